@@ -0,0 +1,144 @@
+//! Generates one test per example declared in each day's manifest in `data/examples` and, with the
+//! `in-process` feature, a registry of the solutions in `src/bin`, so the main binary can run them in-process.
+use std::{env, fs, path::Path};
+
+#[allow(dead_code, unused_imports)]
+#[path = "src/template/examples.rs"]
+mod examples;
+
+use examples::Examples;
+
+/// A solution binary in `src/bin`, named `NN.rs` for the default season or `YYYY_NN.rs` for other seasons.
+struct Bin {
+    year: Option<u16>,
+    day: u8,
+    name: String,
+    path: String,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src/bin");
+
+    let mut bins: Vec<Bin> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    if path.extension()? != "rs" {
+                        return None;
+                    }
+                    let name = path.file_stem()?.to_str()?.to_string();
+                    let (year, day) = parse_bin_name(&name)?;
+                    Some(Bin {
+                        year,
+                        day,
+                        name,
+                        path: path.to_str().unwrap().to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    bins.sort_unstable_by_key(|bin| (bin.year, bin.day));
+
+    let mut modules = String::new();
+    let mut entries = String::new();
+
+    let is_in_process = env::var_os("CARGO_FEATURE_IN_PROCESS").is_some();
+
+    for Bin {
+        year,
+        day,
+        name,
+        path,
+    } in bins
+        .iter()
+        .filter(|bin| is_in_process && can_run_in_process(bin))
+    {
+        modules.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {path:?}]\nmod day_{name};\n"
+        ));
+        entries.push_str(&format!(
+            "    Solution {{ puzzle: Puzzle {{ year: {year:?}, day: advent_of_code::day!({day}) }}, run: day_{name}::__run_in_process }},\n"
+        ));
+    }
+
+    let registry = format!(
+        "// @generated by build.rs\n\
+         use advent_of_code::template::runner::Solution;\n\
+         use advent_of_code::template::Puzzle;\n\n\
+         {modules}\n\
+         pub const SOLUTIONS: &[Solution] = &[\n{entries}];\n"
+    );
+
+    let out_dir = Path::new(&env::var("OUT_DIR").unwrap()).to_path_buf();
+    fs::write(out_dir.join("solutions.rs"), registry).unwrap();
+
+    let mut example_dirs: Vec<String> =
+        bins.iter().map(|bin| Examples::get_dir(bin.year)).collect();
+    example_dirs.dedup();
+
+    for dir in example_dirs {
+        println!("cargo:rerun-if-changed={dir}");
+    }
+
+    for bin in &bins {
+        fs::write(
+            out_dir.join(format!("examples_{}.rs", bin.name)),
+            example_tests(bin),
+        )
+        .unwrap();
+    }
+}
+
+/// Solutions are compiled as modules of the main binary, so paths relative to `crate::` would resolve to the main binary.
+/// These solutions are left out of the registry and run as separate binaries.
+fn can_run_in_process(bin: &Bin) -> bool {
+    fs::read_to_string(&bin.path).is_ok_and(|source| !source.contains("crate::"))
+}
+
+/// Parses the season and day of a binary name like `01` or `2022_01`.
+fn parse_bin_name(name: &str) -> Option<(Option<u16>, u8)> {
+    let (year, day) = match name.split_once('_') {
+        Some((year, day)) if year.len() == 4 => (Some(year.parse().ok()?), day),
+        Some(_) => return None,
+        None => (None, name),
+    };
+
+    let day = day.parse::<u8>().ok().filter(|d| (1..=25).contains(d))?;
+    (name.len() == 2 || year.is_some()).then_some((year, day))
+}
+
+/// Generates a test for each example and part in the manifest of a day.
+fn example_tests(bin: &Bin) -> String {
+    let manifest_path = Examples::get_path(bin.year, format!("{:02}", bin.day));
+    let examples = Examples::read_from_file(&manifest_path).unwrap_or_else(|e| panic!("{e}"));
+
+    let mut tests = String::from("// @generated by build.rs\n");
+
+    for example in &examples.data {
+        for (part, func) in [(1, "part_one"), (2, "part_two")] {
+            let Some(expected) = example.expected(part) else {
+                continue;
+            };
+
+            let suffix = example.index.map_or(String::new(), |i| format!("_{i}"));
+            tests.push_str(&format!(
+                "\n#[test]\n\
+                 fn {func}_example{suffix}() {{\n\
+                 \x20   let input = advent_of_code::template::read_file_indexed(\"examples\", super::PUZZLE, {index:?});\n\
+                 \x20   let result = super::{func}(&input).map(|x| x.to_string());\n\
+                 \x20   assert_eq!(result.as_deref(), {expected:?});\n\
+                 }}\n",
+                index = example.index,
+            ));
+        }
+    }
+
+    tests
+}
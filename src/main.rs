@@ -1,87 +1,184 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, extract, read, scaffold, solve, stars, time, verify,
+};
+use advent_of_code::template::runner::Solution;
+use advent_of_code::template::{Error, DEFAULT_ANSWER_TYPE, DEFAULT_TEMPLATE};
 use args::{parse, AppArguments};
+use std::process;
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::Puzzle;
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::runner::InputSource;
+    use advent_of_code::template::{season, Day, Error, Puzzle, ReportFormat};
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: Puzzle,
         },
         Read {
-            day: Day,
+            puzzle: Puzzle,
+        },
+        Extract {
+            puzzle: Puzzle,
         },
         Scaffold {
-            day: Day,
+            puzzle: Puzzle,
             download: bool,
+            template: Option<String>,
+            answer_type: Option<String>,
+            dry_run: bool,
+            force: bool,
         },
         Solve {
-            day: Day,
+            puzzle: Puzzle,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            format: Option<String>,
+            part: Option<u8>,
+            time: bool,
+            input: InputSource,
         },
         All {
+            year: Option<u16>,
             release: bool,
+            jobs: usize,
+            timeout: Option<Duration>,
+            per_bin: bool,
         },
         Time {
+            year: Option<u16>,
             all: bool,
             day: Option<Day>,
             store: bool,
+            compare: Option<f64>,
+            dhat: bool,
+            timeout: Option<Duration>,
+            per_bin: bool,
+            report: Option<ReportFormat>,
+        },
+        Verify {
+            year: Option<u16>,
+            day: Option<Day>,
+            release: bool,
+            accept: bool,
+            timeout: Option<Duration>,
+            per_bin: bool,
+        },
+        Examples {
+            year: Option<u16>,
+            day: Option<Day>,
+            timeout: Option<Duration>,
+        },
+        Stars {
+            year: Option<u16>,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            year: Option<u16>,
+        },
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    pub fn parse() -> Result<AppArguments, Error> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+        // every command works on a single season, the default one unless `--year` is passed.
+        let requested_year = args.opt_value_from_str("--year")?;
+        let year = season(requested_year);
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
+                year,
                 release: args.contains("--release"),
+                jobs: parse_jobs(&mut args)?,
+                timeout: parse_timeout(&mut args)?,
+                per_bin: args.contains("--per-bin"),
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let compare = args.contains("--compare");
+                let threshold = args.opt_value_from_str("--threshold")?.unwrap_or(10.0);
+                let dhat = args.contains("--dhat");
+                let timeout = parse_timeout(&mut args)?;
+                let per_bin = args.contains("--per-bin");
+                let report = args.opt_value_from_str("--report")?;
 
                 AppArguments::Time {
+                    year,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    compare: compare.then_some(threshold),
+                    dhat,
+                    timeout,
+                    per_bin,
+                    report,
                 }
             }
+            Some("verify") => {
+                let release = args.contains("--release");
+                let accept = args.contains("--accept");
+                let timeout = parse_timeout(&mut args)?;
+                let per_bin = args.contains("--per-bin");
+
+                AppArguments::Verify {
+                    year,
+                    day: args.opt_free_from_str()?,
+                    release,
+                    accept,
+                    timeout,
+                    per_bin,
+                }
+            }
+            Some("examples") => {
+                let timeout = parse_timeout(&mut args)?;
+
+                AppArguments::Examples {
+                    year,
+                    day: args.opt_free_from_str()?,
+                    timeout,
+                }
+            }
+            Some("stars") => AppArguments::Stars { year },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: Puzzle::new(year, args.free_from_str()?),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: Puzzle::new(year, args.free_from_str()?),
+            },
+            Some("extract") => AppArguments::Extract {
+                puzzle: Puzzle::new(year, args.free_from_str()?),
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                puzzle: Puzzle::new(year, args.free_from_str()?),
                 download: args.contains("--download"),
+                template: args.opt_value_from_str("--template")?,
+                answer_type: args.opt_value_from_str("--answer-type")?,
+                dry_run: args.contains("--dry-run"),
+                force: args.contains("--force"),
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: Puzzle::new(year, args.free_from_str()?),
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                format: args.opt_value_from_str("--format")?,
+                part: args.opt_value_from_str("--part")?,
+                time: args.contains("--time"),
+                // last, the index of `--example` is a free argument.
+                input: InputSource::from_args(&mut args)?,
             },
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some("today") => AppArguments::Today {
+                year: requested_year,
+            },
+            Some(x) => return Err(Error::Usage(format!("Unknown command: {x}"))),
+            None => return Err(Error::Usage("No command specified.".into())),
         };
 
         let remaining = args.finish();
@@ -91,48 +188,160 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Parses `--jobs <n>`, the number of days that run at the same time.
+    fn parse_jobs(args: &mut pico_args::Arguments) -> Result<usize, Error> {
+        match args.opt_value_from_str("--jobs")? {
+            Some(0) => Err(Error::Usage("--jobs must be at least 1.".into())),
+            jobs => Ok(jobs.unwrap_or(1)),
+        }
+    }
+
+    /// Parses `--timeout <seconds>` into a duration.
+    fn parse_timeout(args: &mut pico_args::Arguments) -> Result<Option<Duration>, Error> {
+        let seconds: Option<f64> = args.opt_value_from_str("--timeout")?;
+        seconds
+            .map(|seconds| {
+                Duration::try_from_secs_f64(seconds)
+                    .ok()
+                    .filter(|timeout| !timeout.is_zero())
+                    .ok_or_else(|| {
+                        Error::Usage("--timeout must be a positive number of seconds.".into())
+                    })
+            })
+            .transpose()
+    }
+}
+
+/// Solutions compiled into this binary with the `in-process` feature, generated by `build.rs`.
+/// They are left out of test and dhat builds, in which case every day runs via `cargo run --bin`.
+#[cfg(all(feature = "in-process", not(any(test, feature = "dhat-heap"))))]
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+#[cfg(not(all(feature = "in-process", not(any(test, feature = "dhat-heap")))))]
+mod solutions {
+    pub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[];
+}
+
+/// Returns the solutions to run in-process, none if `--per-bin` was passed.
+fn solutions(per_bin: bool) -> &'static [Solution] {
+    if per_bin {
+        &[]
+    } else {
+        solutions::SOLUTIONS
+    }
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+    if let Err(e) = parse().and_then(run) {
+        eprintln!("{e}");
+        process::exit(e.exit_code());
+    }
+}
+
+fn run(args: AppArguments) -> Result<(), Error> {
+    match args {
+        AppArguments::All {
+            year,
+            release,
+            jobs,
+            timeout,
+            per_bin,
+        } => all::handle(year, release, jobs, timeout, solutions(per_bin)),
+        AppArguments::Time {
+            year,
+            report: Some(format),
+            ..
+        } => time::report(year, format),
+        AppArguments::Time {
+            year,
+            day,
+            all,
+            store,
+            compare,
+            dhat,
+            timeout,
+            per_bin,
+            report: None,
+        } => time::handle(
+            year,
+            day,
+            all,
+            store,
+            compare.map(|threshold| time::Compare { threshold }),
+            dhat,
+            timeout,
+            solutions(per_bin),
+        ),
+        AppArguments::Verify {
+            year,
+            day,
+            release,
+            accept,
+            timeout,
+            per_bin,
+        } => verify::handle(year, day, release, accept, timeout, solutions(per_bin)),
+        AppArguments::Examples { year, day, timeout } => {
+            examples::handle(year, day, timeout, solutions(false))
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
-                if download {
-                    download::handle(day);
-                }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+        AppArguments::Stars { year } => stars::handle(year),
+        AppArguments::Download { puzzle } => download::handle(puzzle),
+        AppArguments::Read { puzzle } => read::handle(puzzle),
+        AppArguments::Extract { puzzle } => extract::handle(puzzle),
+        AppArguments::Scaffold {
+            puzzle,
+            download,
+            template,
+            answer_type,
+            dry_run,
+            force,
+        } => {
+            // download first, so the template can use the title of the puzzle.
+            if download && !dry_run {
+                download::handle(puzzle)?;
             }
-        },
-    };
+            scaffold::handle(
+                puzzle,
+                template.as_deref().unwrap_or(DEFAULT_TEMPLATE),
+                answer_type.as_deref().unwrap_or(DEFAULT_ANSWER_TYPE),
+                dry_run,
+                force,
+            )
+        }
+        AppArguments::Solve {
+            puzzle,
+            release,
+            dhat,
+            submit,
+            format,
+            part,
+            time,
+            input,
+        } => solve::handle(
+            puzzle,
+            release,
+            dhat,
+            submit,
+            format.as_deref(),
+            part,
+            time,
+            &input,
+        ),
+        #[cfg(feature = "today")]
+        AppArguments::Today { year } => {
+            let Some(puzzle) = Puzzle::today(year) else {
+                return Err(Error::Usage(
+                    "`today` command can only be run between the 1st and \
+                    the 25th of december. Please use `scaffold` with a specific day."
+                        .into(),
+                ));
+            };
+
+            download::handle(puzzle)?;
+            scaffold::handle(puzzle, DEFAULT_TEMPLATE, DEFAULT_ANSWER_TYPE, false, false)?;
+            read::handle(puzzle)
+        }
+    }
 }
@@ -0,0 +1,502 @@
+/// Client for the Advent of Code website, used to download inputs and puzzles and to submit answers.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::Puzzle;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+const SESSION_FILE: &str = ".adventofcode.session";
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    YearNotFound,
+    BadStatus(u16),
+    Transport(String),
+    UnexpectedResponse(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "no session cookie found. Set \"AOC_SESSION\" or save it to \"~/{SESSION_FILE}\"."
+            ),
+            AocClientError::YearNotFound => {
+                write!(f, "no year given. Set \"AOC_YEAR\" or pass \"--year\".")
+            }
+            AocClientError::BadStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::UnexpectedResponse(e) => write!(f, "unexpected response: {e}"),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, _) => AocClientError::BadStatus(status),
+            ureq::Error::Transport(e) => AocClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// Outcome of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, the answer was not checked.
+    Wait,
+    /// The part is already solved, the answer was not checked.
+    AlreadySolved,
+}
+
+impl Outcome {
+    /// Whether the answer was checked and found to be wrong.
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Outcome::Incorrect | Outcome::TooHigh | Outcome::TooLow
+        )
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::Incorrect => write!(f, "incorrect"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::Wait => write!(f, "wait"),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+        }
+    }
+}
+
+/// What the server said about a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubmitResponse {
+    pub outcome: Outcome,
+    /// How long to wait before the next answer is accepted, if the server asked to.
+    pub wait: Option<Duration>,
+}
+
+impl Display for SubmitResponse {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.outcome {
+            Outcome::Correct => write!(f, "That's the right answer!")?,
+            Outcome::Incorrect => write!(f, "That's not the right answer.")?,
+            Outcome::TooHigh => write!(f, "That's not the right answer, it is too high.")?,
+            Outcome::TooLow => write!(f, "That's not the right answer, it is too low.")?,
+            Outcome::Wait => write!(f, "You gave an answer too recently.")?,
+            Outcome::AlreadySolved => {
+                write!(f, "This part is already solved or not unlocked yet.")?;
+            }
+        }
+
+        match self.wait {
+            Some(wait) => write!(f, " Wait {}s before trying again.", wait.as_secs()),
+            None => Ok(()),
+        }
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.trim().into(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    /// Creates a client for the base url in `AOC_BASE_URL`, defaulting to the Advent of Code website.
+    /// The session cookie is read from `AOC_SESSION`, or from a `.adventofcode.session` file in the
+    /// home or config directory.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = read_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Downloads the puzzle input.
+    pub fn get_input(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.puzzle_url(puzzle)?);
+        self.get(&url)
+    }
+
+    /// Downloads the puzzle description as markdown. The second part is included once it is unlocked.
+    pub fn get_puzzle(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let html = self.get(&self.puzzle_url(puzzle)?)?;
+        parse_puzzle(&html)
+    }
+
+    pub fn submit(
+        &self,
+        puzzle: Puzzle,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmitResponse, AocClientError> {
+        let url = format!("{}/answer", self.puzzle_url(puzzle)?);
+        let level = part.to_string();
+
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &level), ("answer", answer)])?
+            .into_string()?;
+
+        parse_submit_response(&html)
+    }
+
+    fn get(&self, url: &str) -> Result<String, AocClientError> {
+        let body = self
+            .agent
+            .get(url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?;
+        Ok(body)
+    }
+
+    fn puzzle_url(&self, puzzle: Puzzle) -> Result<String, AocClientError> {
+        let year = puzzle.resolved_year().ok_or(AocClientError::YearNotFound)?;
+        Ok(format!(
+            "{}/{year}/day/{}",
+            self.base_url,
+            puzzle.day.into_inner()
+        ))
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// Downloads input and puzzle description and writes them to the data directory.
+pub fn download(client: &AocClient, puzzle: Puzzle) -> Result<(), AocClientError> {
+    let input_path = puzzle.input_path();
+    let puzzle_path = puzzle.puzzle_path();
+
+    let input = client.get_input(puzzle)?;
+    let description = client.get_puzzle(puzzle)?;
+
+    write_file(&input_path, &input)?;
+    write_file(&puzzle_path, &description)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Writes a file, creating its directory first, e.g. the data directory of a season that has no files yet.
+pub fn write_file(path: &str, contents: &str) -> Result<(), io::Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)
+}
+
+fn read_session() -> Option<String> {
+    if let Some(session) = env::var("AOC_SESSION")
+        .ok()
+        .filter(|s| !s.trim().is_empty())
+    {
+        return Some(session);
+    }
+
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    [
+        home.map(|dir| dir.join(SESSION_FILE)),
+        config.map(|dir| dir.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .filter_map(|path| fs::read_to_string(path).ok())
+    .map(|session| session.trim().to_string())
+    .find(|session| !session.is_empty())
+}
+
+/// Returns the inner html of all `<article>` elements of a page.
+fn find_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(content_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(content_end) = rest[content_start..]
+            .find("</article>")
+            .map(|i| content_start + i)
+        else {
+            break;
+        };
+
+        articles.push(&rest[content_start..content_end]);
+        rest = &rest[content_end..];
+    }
+
+    articles
+}
+
+fn parse_puzzle(html: &str) -> Result<String, AocClientError> {
+    let articles = find_articles(html);
+
+    if articles.is_empty() {
+        return Err(AocClientError::UnexpectedResponse(
+            "puzzle description not found.".into(),
+        ));
+    }
+
+    Ok(articles
+        .iter()
+        .map(|article| html2md::parse_html(article).trim().to_string())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+        + "\n")
+}
+
+fn parse_submit_response(html: &str) -> Result<SubmitResponse, AocClientError> {
+    let text = find_articles(html).first().copied().unwrap_or(html);
+
+    let outcome = if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::Wait
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        return Err(AocClientError::UnexpectedResponse(
+            "could not interpret the response to the submission.".into(),
+        ));
+    };
+
+    let wait = match outcome {
+        Outcome::Wait => Some(parse_wait_time(text)),
+        _ if outcome.is_wrong() => parse_retry_time(text),
+        _ => None,
+    };
+
+    Ok(SubmitResponse { outcome, wait })
+}
+
+/// Parses a wait time like "You have 1m 23s left to wait.".
+fn parse_wait_time(text: &str) -> Duration {
+    let Some(end) = text.find(" left to wait") else {
+        return Duration::ZERO;
+    };
+    let Some(start) = text[..end]
+        .rfind("You have ")
+        .map(|i| i + "You have ".len())
+    else {
+        return Duration::ZERO;
+    };
+
+    let seconds = text[start..end]
+        .split_whitespace()
+        .filter_map(|token| {
+            let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum();
+
+    Duration::from_secs(seconds)
+}
+
+/// Parses the cooldown after a wrong answer, like "please wait one minute before trying again".
+fn parse_retry_time(text: &str) -> Option<Duration> {
+    let start = text.find("wait ")? + "wait ".len();
+    let end = start + text[start..].find(" before trying again")?;
+
+    let mut words = text[start..end].split_whitespace();
+    let value = match words.next()? {
+        "one" | "a" | "an" => 1,
+        value => value.parse().ok()?,
+    };
+
+    match words.next()?.trim_end_matches('s') {
+        "second" => Some(Duration::from_secs(value)),
+        "minute" => Some(Duration::from_secs(value * 60)),
+        "hour" => Some(Duration::from_secs(value * 3600)),
+        _ => None,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use super::{parse_puzzle, parse_submit_response, AocClient, Outcome};
+    use crate::{day, template::Puzzle};
+
+    fn response(text: &str) -> String {
+        format!("<main>\n<article><p>{text}</p></article>\n</main>")
+    }
+
+    /// Serves a single request, returning the request line and body through the join handle.
+    fn mock_server(body: &'static str) -> (String, thread::JoinHandle<(String, String)>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+
+            let mut content_length = 0;
+            let mut cookie = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end();
+                if line.is_empty() {
+                    break;
+                }
+                let (name, value) = line.split_once(": ").unwrap();
+                match name.to_lowercase().as_str() {
+                    "content-length" => content_length = value.parse().unwrap(),
+                    "cookie" => cookie = value.to_string(),
+                    _ => {}
+                }
+            }
+            assert_eq!(cookie, "session=abc");
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            (
+                request_line.trim_end().to_string(),
+                String::from_utf8(request_body).unwrap(),
+            )
+        });
+
+        (url, handle)
+    }
+
+    fn puzzle() -> Puzzle {
+        Puzzle {
+            year: Some(2022),
+            day: day!(1),
+        }
+    }
+
+    #[test]
+    fn parses_submit_responses() {
+        let cases = [
+            ("That's the right answer! You are one gold star closer.", Outcome::Correct, None),
+            ("That's not the right answer. If you're stuck, make sure you're using the full input data.", Outcome::Incorrect, None),
+            ("That's not the right answer; your answer is too high. Please wait one minute before trying again.", Outcome::TooHigh, Some(60)),
+            ("That's not the right answer; your answer is too low. Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.", Outcome::TooLow, Some(300)),
+            ("You don't seem to be solving the right level.  Did you already complete it?", Outcome::AlreadySolved, None),
+            ("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 23s left to wait.", Outcome::Wait, Some(83)),
+            ("You gave an answer too recently. You have 42s left to wait.", Outcome::Wait, Some(42)),
+        ];
+
+        for (text, outcome, wait) in cases {
+            let response = parse_submit_response(&response(text)).unwrap();
+            assert_eq!(response.outcome, outcome);
+            assert_eq!(response.wait, wait.map(Duration::from_secs));
+        }
+
+        assert!(parse_submit_response("<main></main>").is_err());
+    }
+
+    #[test]
+    fn parses_puzzle_articles() {
+        let html = "<main><article class=\"day-desc\"><h2>--- Day 1: Test ---</h2><p>Part <em>one</em>.</p></article><p>Your puzzle answer was <code>1</code>.</p><article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Part two.</p></article></main>";
+        let markdown = parse_puzzle(html).unwrap();
+        assert!(markdown.contains("Day 1: Test"));
+        assert!(markdown.contains("Part *one*."));
+        assert!(markdown.contains("Part Two"));
+        assert!(!markdown.contains("Your puzzle answer"));
+        assert!(parse_puzzle("<main></main>").is_err());
+    }
+
+    #[test]
+    fn downloads_input_from_base_url() {
+        let (url, server) = mock_server("1\n2\n3\n");
+        let input = AocClient::new(&url, "abc").get_input(puzzle()).unwrap();
+        assert_eq!(input, "1\n2\n3\n");
+        assert_eq!(server.join().unwrap().0, "GET /2022/day/1/input HTTP/1.1");
+    }
+
+    #[test]
+    fn submits_answer_to_base_url() {
+        let (url, server) = mock_server("<article><p>That's the right answer!</p></article>");
+        let response = AocClient::new(&url, "abc")
+            .submit(puzzle(), 2, "42")
+            .unwrap();
+        assert_eq!(response.outcome, Outcome::Correct);
+
+        let (request_line, body) = server.join().unwrap();
+        assert_eq!(request_line, "POST /2022/day/1/answer HTTP/1.1");
+        assert_eq!(body, "level=2&answer=42");
+    }
+}
@@ -1,5 +1,29 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::time::Duration;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{
+    all_days,
+    run_multi::{run_multi, RunOptions},
+    runner::Solution,
+    Error,
+};
+
+pub fn handle(
+    year: Option<u16>,
+    is_release: bool,
+    jobs: usize,
+    timeout: Option<Duration>,
+    solutions: &[Solution],
+) -> Result<(), Error> {
+    let options = RunOptions {
+        year,
+        is_release,
+        is_timed: false,
+        is_dhat: false,
+        solutions,
+        jobs,
+        timeout,
+    };
+
+    run_multi(&all_days().collect(), &options);
+    Ok(())
 }
@@ -1,14 +1,8 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::aoc_client::{self, AocClient};
+use crate::template::{Error, Puzzle};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
-
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+pub fn handle(puzzle: Puzzle) -> Result<(), Error> {
+    let client = AocClient::from_env()?;
+    aoc_client::download(&client, puzzle)?;
+    Ok(())
 }
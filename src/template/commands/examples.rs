@@ -0,0 +1,233 @@
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::template::examples::{Example, Examples};
+use crate::template::run_multi::{
+    child_commands, find_solution, in_process::run_with_input, DayOutput, RunOptions,
+};
+use crate::template::runner::{InputSource, PartResult, Solution};
+use crate::template::timings::Status;
+use crate::template::{all_days, Day, Error, Puzzle, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// Runs solutions against their examples and checks the answers declared in the example manifests.
+/// Unlike `cargo test`, no tests are built. Solutions compiled into the main binary run in-process,
+/// other solutions run as separate binaries.
+pub fn handle(
+    year: Option<u16>,
+    day: Option<Day>,
+    timeout: Option<Duration>,
+    solutions: &[Solution],
+) -> Result<(), Error> {
+    let puzzles: Vec<Puzzle> = all_days()
+        .filter(|d| day.is_none_or(|day| day == *d))
+        .map(|day| Puzzle { year, day })
+        .filter(|puzzle| Path::new(&puzzle.bin_path()).exists())
+        .collect();
+
+    if let Some(day) = day.filter(|_| puzzles.is_empty()) {
+        return Err(Error::Usage(format!(
+            "Day {} is not scaffolded.",
+            Puzzle { year, day }
+        )));
+    }
+
+    let options = RunOptions {
+        year,
+        is_release: !cfg!(debug_assertions),
+        is_timed: false,
+        is_dhat: false,
+        solutions,
+        jobs: 1,
+        timeout,
+    };
+
+    let mut passed = 0;
+    let mut failed = 0;
+
+    for puzzle in puzzles {
+        let manifest_path = Examples::get_path(year, puzzle.day);
+        let examples = match Examples::read_from_file(&manifest_path) {
+            Ok(examples) => examples,
+            Err(e) => {
+                eprintln!("Could not parse {e}");
+                failed += 1;
+                continue;
+            }
+        };
+
+        if examples.data.is_empty() {
+            if day.is_some() {
+                println!("No examples declared in \"{manifest_path}\".");
+            }
+            continue;
+        }
+
+        println!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET}");
+        println!("------");
+
+        let (day_passed, day_failed) = check_examples(puzzle, &examples, |example, input| {
+            run_example(puzzle, example.index, input, &options)
+        })?;
+        passed += day_passed;
+        failed += day_failed;
+
+        println!();
+    }
+
+    if failed > 0 {
+        return Err(Error::Failed(format!(
+            "{failed} example(s) failed, {passed} passed."
+        )));
+    }
+
+    println!("{ANSI_GREEN}{passed} example(s) passed.{ANSI_RESET}");
+    Ok(())
+}
+
+/// Checks the answers of a day against the expected answers of its examples.
+/// Returns the number of passed and failed parts, an example file that does not exist counts as one failure.
+fn check_examples(
+    puzzle: Puzzle,
+    examples: &Examples,
+    mut run: impl FnMut(&Example, String) -> Result<(Vec<PartResult>, Status), Error>,
+) -> Result<(usize, usize), Error> {
+    let mut passed = 0;
+    let mut failed = 0;
+
+    for example in &examples.data {
+        let file_name = example.file_name(puzzle.day);
+        let path = format!("{}/{file_name}", Examples::get_dir(puzzle.year));
+
+        let Ok(input) = fs::read_to_string(path) else {
+            println!("{ANSI_RED}{file_name}: example file does not exist.{ANSI_RESET}");
+            failed += 1;
+            continue;
+        };
+
+        let (results, status) = run(example, input)?;
+
+        for part in 1..=2 {
+            let Some(expected) = example.expected(part) else {
+                continue;
+            };
+
+            let actual = match status {
+                Status::Panic => Err("panicked"),
+                Status::Timeout => Err("timed out"),
+                Status::Error => Err("failed to run"),
+                Status::Ok | Status::Unsolved => Ok(results
+                    .iter()
+                    .find(|r| r.part == part)
+                    .and_then(|r| r.answer.as_deref())),
+            };
+
+            let label = format!("{file_name} Part {part}");
+
+            match actual {
+                Ok(actual) if actual == expected => {
+                    passed += 1;
+                    println!(
+                        "{ANSI_GREEN}✔ {label}: {}{ANSI_RESET}",
+                        actual.unwrap_or("✖")
+                    );
+                }
+                Ok(actual) => {
+                    failed += 1;
+                    println!(
+                        "{ANSI_RED}✘ {label}: expected {}, got {}{ANSI_RESET}",
+                        expected.unwrap_or("✖"),
+                        actual.unwrap_or("✖")
+                    );
+                }
+                Err(reason) => {
+                    failed += 1;
+                    println!("{ANSI_RED}✘ {label}: {reason}{ANSI_RESET}");
+                }
+            }
+        }
+    }
+
+    Ok((passed, failed))
+}
+
+/// Runs a solution against an example, in-process if it is compiled into the main binary.
+fn run_example(
+    puzzle: Puzzle,
+    index: Option<u8>,
+    input: String,
+    options: &RunOptions,
+) -> Result<(Vec<PartResult>, Status), Error> {
+    if let Some(solution) = find_solution(puzzle, options) {
+        return Ok(run_with_input(solution, input, false)?);
+    }
+
+    let mut output = DayOutput::buffered();
+    let result =
+        child_commands::run_solution(puzzle, &InputSource::Example(index), options, &mut output)?;
+
+    // the output of the solution is only of interest if it did not complete.
+    if result.1 != Status::Ok {
+        output.flush();
+    }
+
+    Ok(result)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::check_examples;
+    use crate::day;
+    use crate::template::examples::{Example, Examples};
+    use crate::template::runner::{BenchStats, PartResult};
+    use crate::template::timings::Status;
+    use crate::template::Puzzle;
+
+    fn get_mock_examples(index: Option<u8>) -> Examples {
+        Examples {
+            data: vec![Example {
+                index,
+                part_1: Some(Some("142".into())),
+                part_2: Some(Some("281".into())),
+            }],
+        }
+    }
+
+    fn get_mock_result(part: u8, answer: &str) -> PartResult {
+        PartResult {
+            day: day!(1),
+            part,
+            answer: Some(answer.into()),
+            stats: BenchStats::single(Duration::ZERO),
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn fails_for_mismatching_answers() {
+        let checked = check_examples(Puzzle::from(day!(1)), &get_mock_examples(None), |_, _| {
+            Ok((
+                vec![get_mock_result(1, "142"), get_mock_result(2, "280")],
+                Status::Ok,
+            ))
+        });
+        assert_eq!(checked.unwrap(), (1, 1));
+    }
+
+    #[test]
+    fn fails_for_missing_example_files() {
+        let mut runs = 0;
+        let checked = check_examples(
+            Puzzle::from(day!(1)),
+            &get_mock_examples(Some(9)),
+            |_, _| {
+                runs += 1;
+                Ok((vec![], Status::Ok))
+            },
+        );
+        assert_eq!(checked.unwrap(), (0, 1));
+        assert_eq!(runs, 0);
+    }
+}
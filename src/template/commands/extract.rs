@@ -0,0 +1,137 @@
+use std::fs;
+
+use crate::template::examples::Examples;
+use crate::template::{markdown, Error, Puzzle};
+
+/// Extracts the examples of a downloaded puzzle description into example files and the example manifest.
+/// Example files that already have other content are kept, as are their expected answers.
+pub fn handle(puzzle: Puzzle) -> Result<(), Error> {
+    let puzzle_path = puzzle.puzzle_path();
+    let Ok(description) = fs::read_to_string(&puzzle_path) else {
+        return Err(Error::Usage(format!(
+            "Puzzle description \"{puzzle_path}\" does not exist. Download it with `cargo download {}` first.",
+            puzzle.day
+        )));
+    };
+
+    let manifest_path = Examples::get_path(puzzle.year, puzzle.day);
+    let mut examples = Examples::read_from_file(&manifest_path).map_err(Error::Parse)?;
+
+    let mut first_input: Option<String> = None;
+
+    for example in markdown::extract_examples(&description) {
+        let part = example.part;
+
+        let Some(input) = example.input else {
+            println!("Part {part}: no example found.");
+            continue;
+        };
+
+        // part two reuses the example of part one, unless it introduces a new one.
+        let index = match &first_input {
+            Some(first) if *first != input => Some(part),
+            _ => None,
+        };
+
+        let path = puzzle.indexed_example_path(index);
+        let is_written = write_example(&path, &input)?;
+        first_input.get_or_insert(input);
+
+        // the answer of the extracted example does not apply to a file with other content.
+        if !is_written {
+            println!("Part {part}: left the expected answer for \"{path}\" unchanged.");
+            continue;
+        }
+
+        if index.is_some() {
+            // the placeholder of the first example would expect no answer for this part.
+            if let Some(first) = examples.data.iter().find(|e| e.index.is_none()) {
+                if first.expected(part) == Some(None) {
+                    examples.set(None, part, None);
+                }
+            }
+        }
+
+        match example.answer {
+            Some(answer) => {
+                println!("Part {part}: expecting {answer} for \"{path}\".");
+                examples.set(index, part, Some(Some(answer)));
+            }
+            None => println!("Part {part}: no expected answer found for \"{path}\"."),
+        }
+    }
+
+    examples.store_file(&manifest_path)?;
+    println!("Updated example manifest \"{manifest_path}\".");
+    Ok(())
+}
+
+/// Writes an example file, unless it already has other content.
+/// Returns whether the file holds the extracted example.
+fn write_example(path: &str, input: &str) -> Result<bool, Error> {
+    match fs::read_to_string(path) {
+        Ok(existing) if existing.trim_end() == input.trim_end() => Ok(true),
+        Ok(existing) if !existing.trim().is_empty() => {
+            println!(
+                "Kept existing example file \"{path}\", it differs from the extracted example."
+            );
+            Ok(false)
+        }
+        _ => {
+            fs::write(path, input)?;
+            println!("Wrote example file \"{path}\".");
+            Ok(true)
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::write_example;
+
+    fn get_mock_path(name: &str, existing: Option<&str>) -> String {
+        let path = env::temp_dir()
+            .join(format!("aoc_extract_{}_{name}.txt", process::id()))
+            .to_string_lossy()
+            .to_string();
+
+        match existing {
+            Some(existing) => fs::write(&path, existing).unwrap(),
+            None => {
+                let _ = fs::remove_file(&path);
+            }
+        }
+
+        path
+    }
+
+    fn read_and_remove(path: &str) -> String {
+        let contents = fs::read_to_string(path).unwrap();
+        fs::remove_file(path).unwrap();
+        contents
+    }
+
+    #[test]
+    fn writes_new_and_empty_examples() {
+        let path = get_mock_path("new", None);
+        assert!(write_example(&path, "1\n2").unwrap());
+        assert_eq!(read_and_remove(&path), "1\n2");
+
+        let path = get_mock_path("empty", Some(""));
+        assert!(write_example(&path, "1\n2").unwrap());
+        assert_eq!(read_and_remove(&path), "1\n2");
+    }
+
+    #[test]
+    fn keeps_examples_with_other_content() {
+        let path = get_mock_path("same", Some("1\n2\n"));
+        assert!(write_example(&path, "1\n2").unwrap());
+        assert_eq!(read_and_remove(&path), "1\n2\n");
+
+        let path = get_mock_path("other", Some("3\n4\n"));
+        assert!(!write_example(&path, "1\n2").unwrap());
+        assert_eq!(read_and_remove(&path), "3\n4\n");
+    }
+}
@@ -1,15 +1,51 @@
-use std::process;
+use std::fs;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::answers::Answers;
+use crate::template::aoc_client::{write_file, AocClient};
+use crate::template::{markdown, Error, Puzzle};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+/// Renders the cached puzzle description. The description is fetched if it is not cached yet, or if
+/// part one is solved and the cached description does not contain part two.
+pub fn handle(puzzle: Puzzle) -> Result<(), Error> {
+    let path = puzzle.puzzle_path();
+    let cached = fs::read_to_string(&path)
+        .ok()
+        .filter(|description| !description.trim().is_empty());
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+    let description = match cached {
+        Some(description) if !is_outdated(puzzle, &description) => description,
+        cached => match (fetch(puzzle), cached) {
+            (Ok(description), _) => description,
+            (Err(e), Some(description)) => {
+                eprintln!("failed to fetch puzzle, showing cached description: {e}\n");
+                description
+            }
+            (Err(e), None) => return Err(e),
+        },
     };
+
+    print!("{}", markdown::render(&description));
+    Ok(())
+}
+
+fn is_outdated(puzzle: Puzzle, description: &str) -> bool {
+    !markdown::has_part_two(description)
+        && Answers::read_from_file(puzzle.year)
+            .is_ok_and(|answers| answers.get(puzzle.day, 1).is_some())
+}
+
+fn fetch(puzzle: Puzzle) -> Result<String, Error> {
+    let client = AocClient::from_env()?;
+
+    println!("Fetching puzzle for day {puzzle}...\n");
+    let description = client.get_puzzle(puzzle)?;
+
+    if let Err(e) = write_file(&puzzle.puzzle_path(), &description) {
+        eprintln!(
+            "failed to write puzzle to \"{}\": {e}",
+            puzzle.puzzle_path()
+        );
+    }
+
+    Ok(description)
 }
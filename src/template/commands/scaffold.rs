@@ -1,69 +1,258 @@
-use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
-};
+use std::{fs, path::Path};
 
-use crate::template::Day;
+use tinyjson::JsonValue;
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::examples::{Example, Examples};
+use crate::template::templates::{self, Placeholders};
+use crate::template::{Error, Puzzle, ANSI_RED, ANSI_RESET};
 
-fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create_new(true).open(path)
+/// A file created by `scaffold`.
+struct Artifact {
+    name: &'static str,
+    path: String,
+    /// Contents of the file, or the reason why they could not be generated.
+    contents: Result<String, String>,
+    /// Placeholders are never written over an existing file, even with `--force`,
+    /// so they do not discard a downloaded input or extracted examples.
+    is_placeholder: bool,
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+/// What happened to an artifact.
+enum Status {
+    Created,
+    Overwritten,
+    Skipped(&'static str),
+    Failed(String),
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Creates the module and data files of a puzzle. Existing files are kept, unless `force` is set for the module file.
+/// With `dry_run` nothing is written and the files that would be created are listed instead.
+pub fn handle(
+    puzzle: Puzzle,
+    template: &str,
+    answer_type: &str,
+    dry_run: bool,
+    force: bool,
+) -> Result<(), Error> {
+    let module_contents = templates::load(template)
+        .map(|module_template| {
+            templates::render(&module_template, &Placeholders::new(puzzle, answer_type))
+        })
+        .map_err(|e| {
+            format!(
+                "could not load template \"{}\" ({e}), available templates: {}",
+                templates::get_path(template),
+                templates::list().join(", ")
+            )
+        });
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    // Matches the template, which returns no answer for either part.
+    let manifest = Examples {
+        data: vec![Example {
+            index: None,
+            part_1: Some(None),
+            part_2: Some(None),
+        }],
     };
+    let manifest_contents = JsonValue::from(manifest)
+        .format()
+        .map_err(|e| e.to_string());
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    let artifacts = [
+        Artifact {
+            name: "module file",
+            path: puzzle.bin_path(),
+            contents: module_contents,
+            is_placeholder: false,
+        },
+        Artifact {
+            name: "input file",
+            path: puzzle.input_path(),
+            contents: Ok(String::new()),
+            is_placeholder: true,
+        },
+        Artifact {
+            name: "example file",
+            path: puzzle.example_path(),
+            contents: Ok(String::new()),
+            is_placeholder: true,
+        },
+        Artifact {
+            name: "example manifest",
+            path: Examples::get_path(puzzle.year, puzzle.day),
+            contents: manifest_contents,
+            is_placeholder: true,
+        },
+    ];
+
+    let mut failed = 0;
+
+    for artifact in &artifacts {
+        let status = scaffold_artifact(artifact, dry_run, force);
+        let verb = match (&status, dry_run) {
+            (Status::Created, false) => "Created",
+            (Status::Created, true) => "Would create",
+            (Status::Overwritten, false) => "Overwrote",
+            (Status::Overwritten, true) => "Would overwrite",
+            (Status::Skipped(_), _) => "Skipped",
+            (Status::Failed(_), _) => "Failed to create",
+        };
+
+        match status {
+            Status::Created | Status::Overwritten => {
+                let empty = match &artifact.contents {
+                    Ok(contents) if contents.is_empty() => "empty ",
+                    _ => "",
+                };
+                println!("{verb} {empty}{} \"{}\"", artifact.name, artifact.path);
+            }
+            Status::Skipped(reason) => {
+                println!("{verb} {} \"{}\": {reason}", artifact.name, artifact.path);
+            }
+            Status::Failed(e) => {
+                failed += 1;
+                eprintln!(
+                    "{ANSI_RED}{verb} {} \"{}\": {e}{ANSI_RESET}",
+                    artifact.name, artifact.path
+                );
+            }
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+    if failed > 0 {
+        return Err(Error::Failed(format!(
+            "---\n{failed} file(s) could not be created."
+        )));
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+
+    println!("---");
+    let year_arg = puzzle
+        .year
+        .map_or(String::new(), |year| format!(" --year {year}"));
+    println!(
+        "🎄 Type `cargo solve {}{year_arg}` to run your solution.",
+        puzzle.day
+    );
+    Ok(())
+}
+
+fn scaffold_artifact(artifact: &Artifact, dry_run: bool, force: bool) -> Status {
+    let contents = match &artifact.contents {
+        Ok(contents) => contents,
+        Err(e) => return Status::Failed(e.clone()),
+    };
+
+    let status = match fs::metadata(&artifact.path) {
+        Err(_) => Status::Created,
+        Ok(_) if force && !artifact.is_placeholder => Status::Overwritten,
+        Ok(metadata) if metadata.len() > 0 && !artifact.is_placeholder => {
+            Status::Skipped("it has content, use --force to overwrite it")
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+        Ok(_) => Status::Skipped("it already exists"),
+    };
+
+    if dry_run || matches!(status, Status::Skipped(_)) {
+        return status;
+    }
+
+    // the data directories of other seasons do not exist until their first day is scaffolded.
+    if let Some(dir) = Path::new(&artifact.path).parent() {
+        if let Err(e) = fs::create_dir_all(dir) {
+            return Status::Failed(e.to_string());
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    match fs::write(&artifact.path, contents) {
+        Ok(()) => status,
+        Err(e) => Status::Failed(e.to_string()),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{scaffold_artifact, Artifact, Status};
+
+    fn get_mock_artifact(name: &str, existing: Option<&str>, is_placeholder: bool) -> Artifact {
+        let path = env::temp_dir()
+            .join(format!("aoc_scaffold_{}_{name}.txt", process::id()))
+            .to_string_lossy()
+            .to_string();
+
+        match existing {
+            Some(existing) => fs::write(&path, existing).unwrap(),
+            None => {
+                let _ = fs::remove_file(&path);
+            }
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+
+        Artifact {
+            name: "file",
+            path,
+            contents: Ok("template".into()),
+            is_placeholder,
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    fn read_and_remove(artifact: &Artifact) -> Option<String> {
+        let contents = fs::read_to_string(&artifact.path).ok();
+        let _ = fs::remove_file(&artifact.path);
+        contents
+    }
+
+    #[test]
+    fn writes_nothing_in_dry_run() {
+        let artifact = get_mock_artifact("dry_run", None, false);
+        assert!(matches!(
+            scaffold_artifact(&artifact, true, false),
+            Status::Created
+        ));
+        assert_eq!(read_and_remove(&artifact), None);
+
+        let artifact = get_mock_artifact("dry_run_force", Some("solution"), false);
+        assert!(matches!(
+            scaffold_artifact(&artifact, true, true),
+            Status::Overwritten
+        ));
+        assert_eq!(read_and_remove(&artifact).as_deref(), Some("solution"));
+    }
+
+    #[test]
+    fn keeps_existing_files() {
+        let artifact = get_mock_artifact("keep", Some("solution"), false);
+        assert!(matches!(
+            scaffold_artifact(&artifact, false, false),
+            Status::Skipped(_)
+        ));
+        assert_eq!(read_and_remove(&artifact).as_deref(), Some("solution"));
+
+        let artifact = get_mock_artifact("create", None, false);
+        assert!(matches!(
+            scaffold_artifact(&artifact, false, false),
+            Status::Created
+        ));
+        assert_eq!(read_and_remove(&artifact).as_deref(), Some("template"));
+    }
+
+    #[test]
+    fn overwrites_only_modules_with_force() {
+        let artifact = get_mock_artifact("force_module", Some("solution"), false);
+        assert!(matches!(
+            scaffold_artifact(&artifact, false, true),
+            Status::Overwritten
+        ));
+        assert_eq!(read_and_remove(&artifact).as_deref(), Some("template"));
+
+        let artifact = get_mock_artifact("force_input", Some("input"), true);
+        assert!(matches!(
+            scaffold_artifact(&artifact, false, true),
+            Status::Skipped(_)
+        ));
+        assert_eq!(read_and_remove(&artifact).as_deref(), Some("input"));
+    }
 }
@@ -0,0 +1,11 @@
+use crate::template::{readme_stars, Error};
+
+/// Regenerates the stars table of the readme from the answers and submissions files of a season.
+pub fn handle(year: Option<u16>) -> Result<(), Error> {
+    if readme_stars::update(year)? {
+        println!("Updated the stars table of the readme.");
+    } else {
+        println!("No solved parts recorded yet, the stars table of the readme was left as is.");
+    }
+    Ok(())
+}
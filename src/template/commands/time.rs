@@ -1,16 +1,43 @@
 use std::collections::HashSet;
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::dashboard::{Column, Dashboard};
+use crate::template::history::Run;
+use crate::template::report::Report;
+use crate::template::run_multi::{collect_memory, collect_timings, run_multi, DayRun, RunOptions};
+use crate::template::runner::Solution;
+use crate::template::timings::{TimingChange, Timings};
+use crate::template::{
+    all_days, readme_benchmarks, readme_stars, Day, Error, ReportFormat, ANSI_BOLD, ANSI_GREEN,
+    ANSI_RED, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Compares fresh benchmarks to stored timings, failing if a part regressed by more than `threshold` percent
+/// or its day did not complete.
+pub struct Compare {
+    pub threshold: f64,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    year: Option<u16>,
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    compare: Option<Compare>,
+    dhat: bool,
+    timeout: Option<Duration>,
+    solutions: &[Solution],
+) -> Result<(), Error> {
+    let stored_timings = Timings::read_from_file(year);
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
                 all_days().collect()
+            } else if compare.is_some() {
+                // when comparing, re-bench every day that has stored timings.
+                stored_timings.data.iter().map(|t| t.day).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
                 all_days()
@@ -21,20 +48,119 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        year,
+        is_release: true,
+        is_timed: true,
+        is_dhat: false,
+        solutions,
+        jobs: 1,
+        timeout,
+    };
+
+    let runs = run_multi(&days_to_run, &options);
+    let mut timings = collect_timings(&runs);
+    timings
+        .history
+        .push(Run::capture(runs.iter().map(DayRun::history).collect()));
+
+    if dhat {
+        // profiling slows down allocations, so heap usage is recorded in a separate, untimed run.
+        println!("\n{ANSI_BOLD}Recording heap usage{ANSI_RESET}\n");
+
+        let options = RunOptions {
+            year,
+            is_release: true,
+            is_timed: false,
+            is_dhat: true,
+            solutions: &[],
+            jobs: 1,
+            timeout,
+        };
+
+        collect_memory(&mut timings, &run_multi(&days_to_run, &options));
+    }
+
+    if let Some(compare) = compare {
+        let changes = stored_timings.compare(&timings);
+        let regressions = print_comparison(&changes, compare.threshold);
+
+        if regressions > 0 {
+            return Err(Error::Failed(format!(
+                "\n{regressions} part(s) regressed by more than {}% or did not complete.",
+                compare.threshold
+            )));
+        }
+    }
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year)?;
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
+        readme_benchmarks::update(merged_timings, year)?;
+        // a benchmarks table that shows the stars was updated already.
+        if !Dashboard::read_from_file().shows(Column::Stars) {
+            if let Err(e) = readme_stars::update(year) {
+                eprintln!("Failed to update the stars in the readme: {e}");
             }
         }
+        println!("Stored updated benchmarks.");
+    }
+
+    Ok(())
+}
+
+/// Prints a report of the stored timings of a season, without benching.
+pub fn report(year: Option<u16>, format: ReportFormat) -> Result<(), Error> {
+    let timings = Timings::read_from_file(year);
+
+    if timings.data.is_empty() {
+        return Err(Error::MissingFile(Timings::get_path(year)));
+    }
+
+    print!("{}", Report::new(&timings, year).render(format));
+    Ok(())
+}
+
+/// Prints the change of every compared part, returns the number of regressions beyond `threshold`.
+fn print_comparison(changes: &[TimingChange], threshold: f64) -> usize {
+    println!("\n{ANSI_BOLD}Comparison to stored timings{ANSI_RESET}");
+    println!("----------------------------");
+
+    if changes.is_empty() {
+        println!("No stored timings to compare to.");
+        return 0;
+    }
+
+    let mut regressions = 0;
+
+    for change in changes {
+        if change.is_regression(threshold) {
+            regressions += 1;
+        }
+
+        let (Some(new), Some(percent)) = (change.new, change.percent()) else {
+            println!(
+                "Day {} Part {}: {:.1?} → {ANSI_RED}{}{ANSI_RESET}",
+                change.day, change.part, change.old, change.status,
+            );
+            continue;
+        };
+
+        let color = if percent > threshold {
+            ANSI_RED
+        } else if percent < -threshold {
+            ANSI_GREEN
+        } else {
+            ""
+        };
+
+        println!(
+            "Day {} Part {}: {:.1?} → {:.1?} ({color}{percent:+.1}%{ANSI_RESET})",
+            change.day, change.part, change.old, new,
+        );
     }
+
+    regressions
 }
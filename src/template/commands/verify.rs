@@ -0,0 +1,211 @@
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::{run_multi, DayRun, RunOptions};
+use crate::template::runner::Solution;
+use crate::template::timings::Status;
+use crate::template::{
+    all_days, readme_stars, Day, Error, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET,
+};
+
+/// Runs solutions and checks their answers against the known-good answers in the answers file.
+/// With `accept`, answers of parts that have no known-good answer yet are recorded and the stars table of the readme is updated.
+pub fn handle(
+    year: Option<u16>,
+    day: Option<Day>,
+    is_release: bool,
+    accept: bool,
+    timeout: Option<Duration>,
+    solutions: &[Solution],
+) -> Result<(), Error> {
+    let mut answers = Answers::read_from_file(year).map_err(Error::Parse)?;
+
+    let days_to_run = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+    let options = RunOptions {
+        year,
+        is_release,
+        is_timed: false,
+        is_dhat: false,
+        solutions,
+        jobs: 1,
+        timeout,
+    };
+
+    let runs = run_multi(&days_to_run, &options);
+
+    println!("\n{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------------");
+
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+    let verification = verify_runs(&mut answers, &days, &runs, accept);
+
+    for failure in &verification.failures {
+        println!("{ANSI_RED}{failure}{ANSI_RESET}");
+    }
+
+    if verification.accepted > 0 {
+        answers.store_file(year)?;
+        if let Err(e) = readme_stars::update(year) {
+            eprintln!("Failed to update the stars in the readme: {e}");
+        }
+        println!("Accepted {} new answer(s).", verification.accepted);
+    }
+
+    if !verification.failures.is_empty() {
+        return Err(Error::Failed(format!(
+            "{} answer(s) could not be verified, {} verified.",
+            verification.failures.len(),
+            verification.verified
+        )));
+    }
+
+    println!(
+        "{ANSI_GREEN}{} answer(s) verified.{ANSI_RESET}",
+        verification.verified
+    );
+    Ok(())
+}
+
+/// Outcome of checking runs against the known-good answers.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Verification {
+    verified: usize,
+    accepted: usize,
+    failures: Vec<String>,
+}
+
+/// Checks every known-good answer of `days` against the results of `runs`.
+/// A part fails if it returned another answer, returned no answer, or its day did not run to completion.
+/// With `accept`, answers of completed days that have no known-good answer yet are added to `answers`.
+fn verify_runs(answers: &mut Answers, days: &[Day], runs: &[DayRun], accept: bool) -> Verification {
+    let mut verification = Verification::default();
+
+    for day in days {
+        let run = runs.iter().find(|run| run.day == *day);
+
+        for part in [1, 2] {
+            let expected = answers.get(*day, part).map(String::from);
+            let actual = run
+                .and_then(|run| run.results.iter().find(|result| result.part == part))
+                .and_then(|result| result.answer.as_deref());
+            let status = run.map(|run| run.status);
+
+            match (expected, status) {
+                (Some(expected), None) => verification.failures.push(format!(
+                    "Day {day} Part {part}: expected {expected}, but the day did not run"
+                )),
+                (
+                    Some(expected),
+                    Some(status @ (Status::Panic | Status::Timeout | Status::Error)),
+                ) => {
+                    verification.failures.push(format!(
+                        "Day {day} Part {part}: expected {expected}, but the day failed with status {status}"
+                    ));
+                }
+                (Some(expected), _) if Some(expected.as_str()) == actual => {
+                    verification.verified += 1;
+                }
+                (Some(expected), _) => verification.failures.push(format!(
+                    "Day {day} Part {part}: expected {expected}, got {}",
+                    actual.unwrap_or("✖")
+                )),
+                (None, Some(Status::Ok)) if accept => {
+                    if let Some(actual) = actual {
+                        verification.accepted += 1;
+                        answers.set(*day, part, actual);
+                    }
+                }
+                (None, _) => {}
+            }
+        }
+    }
+
+    verification
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::verify_runs;
+    use crate::day;
+    use crate::template::answers::Answers;
+    use crate::template::run_multi::DayRun;
+    use crate::template::runner::{BenchStats, PartResult};
+    use crate::template::timings::Status;
+    use crate::template::Day;
+
+    fn get_mock_run(day: Day, answers: &[Option<&str>], status: Status) -> DayRun {
+        DayRun {
+            day,
+            results: answers
+                .iter()
+                .zip(1..)
+                .map(|(answer, part)| PartResult {
+                    day,
+                    part,
+                    answer: answer.map(Into::into),
+                    stats: BenchStats::single(Duration::ZERO),
+                    memory: None,
+                })
+                .collect(),
+            status,
+        }
+    }
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.set(day!(1), 1, "1");
+        answers.set(day!(1), 2, "2");
+        answers
+    }
+
+    #[test]
+    fn verifies_matching_answers() {
+        let runs = [get_mock_run(day!(1), &[Some("1"), Some("2")], Status::Ok)];
+        let verification = verify_runs(&mut get_mock_answers(), &[day!(1)], &runs, false);
+        assert_eq!(verification.verified, 2);
+        assert!(verification.failures.is_empty());
+    }
+
+    #[test]
+    fn fails_for_mismatching_and_missing_parts() {
+        let runs = [get_mock_run(day!(1), &[Some("3")], Status::Ok)];
+        let verification = verify_runs(&mut get_mock_answers(), &[day!(1)], &runs, false);
+        assert_eq!(verification.verified, 0);
+        assert_eq!(
+            verification.failures,
+            vec![
+                "Day 01 Part 1: expected 1, got 3",
+                "Day 01 Part 2: expected 2, got ✖"
+            ]
+        );
+    }
+
+    #[test]
+    fn fails_for_days_that_did_not_complete() {
+        let runs = [get_mock_run(day!(1), &[Some("1")], Status::Panic)];
+        let verification = verify_runs(&mut get_mock_answers(), &[day!(1)], &runs, false);
+        assert_eq!(verification.verified, 0);
+        assert_eq!(verification.failures.len(), 2);
+        assert!(verification.failures[0].ends_with("failed with status panic"));
+
+        let verification = verify_runs(&mut get_mock_answers(), &[day!(1)], &[], false);
+        assert_eq!(verification.failures.len(), 2);
+        assert!(verification.failures[0].ends_with("the day did not run"));
+    }
+
+    #[test]
+    fn accepts_new_answers_of_completed_days() {
+        let mut answers = Answers::default();
+        let runs = [
+            get_mock_run(day!(1), &[Some("1"), None], Status::Ok),
+            get_mock_run(day!(2), &[Some("3")], Status::Timeout),
+        ];
+        let verification = verify_runs(&mut answers, &[day!(1), day!(2)], &runs, true);
+        assert_eq!(verification.accepted, 1);
+        assert_eq!(answers.get(day!(1), 1), Some("1"));
+        assert_eq!(answers.get(day!(2), 1), None);
+    }
+}
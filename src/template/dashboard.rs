@@ -0,0 +1,332 @@
+/// Layout of the benchmarks table in the readme, configured in `data/readme.json`.
+use std::{collections::HashMap, fs, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::aoc_client::Outcome;
+use crate::template::readme_benchmarks::Error;
+use crate::template::readme_stars::collect_stars;
+use crate::template::submissions::Submissions;
+use crate::template::{all_days, data_dir, Day, Puzzle};
+
+/// A column of the table, the day column is always shown first.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Stars,
+    Part1,
+    Part2,
+    /// Peak heap usage and allocations of both parts, only shown if they were recorded.
+    Memory,
+    /// Lines of code of the solution, without blank lines and comments.
+    Lines,
+    /// Date of the last accepted answer.
+    Solved,
+    /// Share of the day in the total time of the season.
+    Share,
+}
+
+impl Column {
+    pub fn headers(self) -> &'static [&'static str] {
+        match self {
+            Column::Stars => &["Stars"],
+            Column::Part1 => &["Part 1"],
+            Column::Part2 => &["Part 2"],
+            Column::Memory => &["Part 1 Memory", "Part 2 Memory"],
+            Column::Lines => &["Lines"],
+            Column::Solved => &["Solved"],
+            Column::Share => &["Share"],
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stars" => Ok(Column::Stars),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "memory" => Ok(Column::Memory),
+            "lines" => Ok(Column::Lines),
+            "solved" => Ok(Column::Solved),
+            "share" => Ok(Column::Share),
+            _ => Err(format!("unknown column \"{s}\"")),
+        }
+    }
+}
+
+/// Order of the rows of the table.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Sort {
+    Day,
+    /// Slowest day first, by total time.
+    Slowest,
+    /// Fastest day first, by total time.
+    Fastest,
+}
+
+impl FromStr for Sort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Sort::Day),
+            "slowest" => Ok(Sort::Slowest),
+            "fastest" => Ok(Sort::Fastest),
+            _ => Err(format!("unknown sort order \"{s}\"")),
+        }
+    }
+}
+
+/// Configuration of the benchmarks table. Without a config file, the table shows the timings
+/// and memory usage of both parts, in day order, followed by the total time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dashboard {
+    /// Heading of the table, `Benchmarks` if not set.
+    pub title: Option<String>,
+    pub columns: Vec<Column>,
+    pub sort: Sort,
+    /// Number of slowest days whose row is highlighted.
+    pub highlight_slowest: usize,
+    /// Whether the total time is shown below the table.
+    pub total: bool,
+}
+
+impl Default for Dashboard {
+    fn default() -> Self {
+        Dashboard {
+            title: None,
+            columns: vec![Column::Part1, Column::Part2, Column::Memory],
+            sort: Sort::Day,
+            highlight_slowest: 0,
+            total: true,
+        }
+    }
+}
+
+impl Dashboard {
+    /// Path of the config file, shared by all seasons.
+    pub fn get_path() -> String {
+        format!("{}/readme.json", data_dir(None))
+    }
+
+    /// Rehydrate the config from a JSON file. If not present, returns the default layout.
+    pub fn read_from_file() -> Self {
+        let path = Dashboard::get_path();
+        match fs::read_to_string(&path) {
+            Ok(s) => match Dashboard::try_from(s) {
+                Ok(dashboard) => dashboard,
+                Err(e) => {
+                    eprintln!("Could not parse {path}: {e}");
+                    Dashboard::default()
+                }
+            },
+            Err(_) => Dashboard::default(),
+        }
+    }
+
+    pub fn shows(&self, column: Column) -> bool {
+        self.columns.contains(&column)
+    }
+}
+
+/// What the table shows about a day besides its timings.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DayDetails {
+    pub part_1_star: bool,
+    pub part_2_star: bool,
+    pub lines: Option<usize>,
+    /// Seconds since the unix epoch.
+    pub solved: Option<u64>,
+}
+
+impl DayDetails {
+    pub fn stars(&self) -> usize {
+        usize::from(self.part_1_star) + usize::from(self.part_2_star)
+    }
+}
+
+/// Collects the stars, lines of code and solve date of the days of a season.
+pub fn collect_details(year: Option<u16>) -> Result<HashMap<Day, DayDetails>, Error> {
+    let answers = Answers::read_from_file(year).map_err(Error::Data)?;
+    let submissions = Submissions::read_from_file(year).map_err(Error::Data)?;
+    let mut details: HashMap<Day, DayDetails> = HashMap::new();
+
+    for stars in collect_stars(&answers, &submissions) {
+        let entry = details.entry(stars.day).or_default();
+        entry.part_1_star = stars.part_1;
+        entry.part_2_star = stars.part_2;
+    }
+
+    for submission in submissions
+        .data
+        .iter()
+        .filter(|s| s.outcome == Outcome::Correct)
+    {
+        let solved = &mut details.entry(submission.day).or_default().solved;
+        *solved = Some(solved.map_or(submission.timestamp, |t| t.max(submission.timestamp)));
+    }
+
+    for day in all_days() {
+        if let Ok(source) = fs::read_to_string(Puzzle { year, day }.bin_path()) {
+            details.entry(day).or_default().lines = Some(count_lines(&source));
+        }
+    }
+
+    Ok(details)
+}
+
+/// Counts the lines of a source file that are neither blank nor comments.
+fn count_lines(source: &str) -> usize {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .count()
+}
+
+/// Formats seconds since the unix epoch as a `YYYY-MM-DD` date in UTC.
+pub fn format_date(timestamp: u64) -> String {
+    // see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = timestamp / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Dashboard {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut dashboard = Dashboard::default();
+
+        if let Some(title) = json.get("title").filter(|v| !v.is_null()) {
+            let title = title
+                .get::<String>()
+                .ok_or("Expected readme.title to be null or a string.")?;
+            dashboard.title = Some(title.clone());
+        }
+
+        if let Some(columns) = json.get("columns") {
+            dashboard.columns = columns
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected readme.columns to be an array.")?
+                .iter()
+                .map(|column| {
+                    column
+                        .get::<String>()
+                        .ok_or_else(|| "Expected readme.columns to contain strings.".to_string())
+                        .and_then(|column| Column::from_str(column))
+                })
+                .collect::<Result<_, _>>()?;
+        }
+
+        if let Some(sort) = json.get("sort") {
+            dashboard.sort = sort
+                .get::<String>()
+                .ok_or_else(|| "Expected readme.sort to be a string.".to_string())
+                .and_then(|sort| Sort::from_str(sort))?;
+        }
+
+        if let Some(highlight) = json.get("highlight_slowest") {
+            dashboard.highlight_slowest = highlight
+                .get::<f64>()
+                .filter(|n| n.fract() == 0_f64 && **n >= 0_f64)
+                .map(|n| *n as usize)
+                .ok_or("Expected readme.highlight_slowest to be a non-negative integer.")?;
+        }
+
+        if let Some(total) = json.get("total") {
+            dashboard.total = *total
+                .get::<bool>()
+                .ok_or("Expected readme.total to be a boolean.")?;
+        }
+
+        Ok(dashboard)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{count_lines, format_date, Column, Dashboard, Sort};
+
+    #[test]
+    fn defaults_to_benchmarks() {
+        let dashboard = Dashboard::try_from("{}".to_string()).unwrap();
+        assert_eq!(dashboard, Dashboard::default());
+    }
+
+    #[test]
+    fn parses_config() {
+        let json = r#"{
+            "title": "Dashboard",
+            "columns": ["stars", "part_1", "part_2", "lines", "solved", "share"],
+            "sort": "slowest",
+            "highlight_slowest": 3,
+            "total": false
+        }"#;
+        let dashboard = Dashboard::try_from(json.to_string()).unwrap();
+        assert_eq!(dashboard.title.as_deref(), Some("Dashboard"));
+        assert_eq!(dashboard.columns[0], Column::Stars);
+        assert_eq!(dashboard.columns.len(), 6);
+        assert_eq!(dashboard.sort, Sort::Slowest);
+        assert_eq!(dashboard.highlight_slowest, 3);
+        assert!(!dashboard.total);
+    }
+
+    #[test]
+    fn rejects_unknown_columns() {
+        let json = r#"{ "columns": ["part_3"] }"#;
+        assert_eq!(
+            Dashboard::try_from(json.to_string()),
+            Err("unknown column \"part_3\"".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_negative_highlights() {
+        let json = r#"{ "highlight_slowest": -1 }"#;
+        assert_eq!(
+            Dashboard::try_from(json.to_string()),
+            Err("Expected readme.highlight_slowest to be a non-negative integer.".to_string())
+        );
+    }
+
+    #[test]
+    fn counts_lines_of_code() {
+        assert_eq!(
+            count_lines("// comment\n\nfn main() {\n    // todo\n}\n"),
+            2
+        );
+    }
+
+    #[test]
+    fn formats_dates() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(1_701_388_800), "2023-12-01");
+        assert_eq!(format_date(1_709_208_000), "2024-02-29");
+    }
+}
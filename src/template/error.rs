@@ -0,0 +1,127 @@
+use std::{fmt::Display, io};
+
+use crate::template::aoc_client::AocClientError;
+use crate::template::{readme_benchmarks, run_multi};
+
+/// Errors returned by the template's commands.
+#[derive(Debug)]
+pub enum Error {
+    /// Invalid command-line input.
+    Usage(String),
+    IO(io::Error),
+    AocClient(AocClientError),
+    Parse(String),
+    MissingFile(String),
+    /// A child process could not be run or exited with a non-zero status.
+    ChildProcess {
+        command: String,
+        code: Option<i32>,
+    },
+    /// A check did not pass, e.g. a verified answer or an example. Details were printed already.
+    Failed(String),
+}
+
+impl Error {
+    /// Exit code of the process when a command fails with this error.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::Usage(_) => 2,
+            Error::ChildProcess {
+                code: Some(code), ..
+            } => *code,
+            _ => 1,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(e) | Error::Failed(e) => write!(f, "{e}"),
+            Error::IO(e) => write!(f, "{e}"),
+            Error::AocClient(e) => write!(f, "{e}"),
+            Error::Parse(e) => write!(f, "Could not parse {e}"),
+            Error::MissingFile(path) => write!(f, "File \"{path}\" does not exist."),
+            Error::ChildProcess {
+                command,
+                code: Some(code),
+            } => write!(f, "`{command}` exited with status {code}."),
+            Error::ChildProcess {
+                command,
+                code: None,
+            } => write!(f, "`{command}` could not be run."),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<AocClientError> for Error {
+    fn from(e: AocClientError) -> Self {
+        Error::AocClient(e)
+    }
+}
+
+impl From<readme_benchmarks::Error> for Error {
+    fn from(e: readme_benchmarks::Error) -> Self {
+        match e {
+            readme_benchmarks::Error::Parser(e) => Error::Parse(format!("README: {e}")),
+            readme_benchmarks::Error::Data(e) => Error::Parse(e),
+            readme_benchmarks::Error::IO(e) => Error::IO(e),
+        }
+    }
+}
+
+impl From<run_multi::Error> for Error {
+    fn from(e: run_multi::Error) -> Self {
+        match e {
+            run_multi::Error::BrokenPipe => Error::ChildProcess {
+                command: "cargo run".into(),
+                code: None,
+            },
+            run_multi::Error::MissingInput(path) => Error::MissingFile(path),
+            run_multi::Error::IO(e) => Error::IO(e),
+        }
+    }
+}
+
+impl From<pico_args::Error> for Error {
+    fn from(e: pico_args::Error) -> Self {
+        Error::Usage(e.to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Error;
+
+    #[test]
+    fn maps_exit_codes() {
+        assert_eq!(Error::Usage("No command specified.".into()).exit_code(), 2);
+        assert_eq!(Error::Failed("1 example(s) failed.".into()).exit_code(), 1);
+        assert_eq!(
+            Error::ChildProcess {
+                command: "cargo run".into(),
+                code: Some(101),
+            }
+            .exit_code(),
+            101
+        );
+        assert_eq!(
+            Error::ChildProcess {
+                command: "cargo run".into(),
+                code: None,
+            }
+            .exit_code(),
+            1
+        );
+    }
+}
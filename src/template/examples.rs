@@ -0,0 +1,257 @@
+//! Expected answers for example inputs, stored in a manifest next to the example files.
+//! This module is also used by the build script to generate tests, so it only depends on `std` and `tinyjson`.
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+/// An example input file and the answers expected for it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    /// Index of the example file, i.e. `None` for `01.txt` and `Some(2)` for `01_2.txt`.
+    pub index: Option<u8>,
+    /// Expected answer for part one. `Some(None)` expects no answer, `None` skips the part.
+    pub part_1: Option<Option<String>>,
+    /// Expected answer for part two. `Some(None)` expects no answer, `None` skips the part.
+    pub part_2: Option<Option<String>>,
+}
+
+impl Example {
+    /// Returns the expected answer for a part, `None` if the part is not checked.
+    pub fn expected(&self, part: u8) -> Option<Option<&str>> {
+        let expected = match part {
+            1 => &self.part_1,
+            2 => &self.part_2,
+            _ => &None,
+        };
+        expected.as_ref().map(Option::as_deref)
+    }
+
+    /// Returns the file name of the example for the given day, e.g. `01_2.txt`.
+    pub fn file_name(&self, day: impl Display) -> String {
+        match self.index {
+            Some(index) => format!("{day}_{index}.txt"),
+            None => format!("{day}.txt"),
+        }
+    }
+}
+
+/// The examples of a single day.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Examples {
+    pub data: Vec<Example>,
+}
+
+impl Examples {
+    /// Directory of the example files of a season, e.g. `data/examples` or `data/2022/examples`.
+    pub fn get_dir(year: Option<u16>) -> String {
+        match year {
+            Some(year) => format!("data/{year}/examples"),
+            None => "data/examples".into(),
+        }
+    }
+
+    /// Path of the manifest for the given day, e.g. `data/examples/01.json`.
+    pub fn get_path(year: Option<u16>, day: impl Display) -> String {
+        format!("{}/{day}.json", Examples::get_dir(year))
+    }
+
+    /// Dehydrate examples to a JSON file.
+    pub fn store_file(&self, path: &str) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Sets the expected answer of a part for the example with the given index, adding the example if needed.
+    pub fn set(&mut self, index: Option<u8>, part: u8, expected: Option<Option<String>>) {
+        let position = match self.data.iter().position(|e| e.index == index) {
+            Some(position) => position,
+            None => {
+                self.data.push(Example {
+                    index,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|e| e.index);
+                self.data.iter().position(|e| e.index == index).unwrap()
+            }
+        };
+
+        let example = &mut self.data[position];
+        match part {
+            1 => example.part_1 = expected,
+            2 => example.part_2 = expected,
+            _ => {}
+        }
+    }
+
+    /// Rehydrate examples from a JSON file. If not present, returns empty examples.
+    pub fn read_from_file(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(s) => Examples::try_from(s).map_err(|e| format!("{path}: {e}")),
+            Err(_) => Ok(Examples::default()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Examples> for JsonValue {
+    fn from(value: Examples) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "examples".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Examples {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("examples")
+            .ok_or("expected JSON document to have key `examples`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.examples` to be an array.")?;
+
+        Ok(Examples {
+            data: json_data
+                .iter()
+                .map(Example::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Example> for JsonValue {
+    fn from(value: &Example) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "index".into(),
+            match value.index {
+                Some(index) => JsonValue::Number(f64::from(index)),
+                None => JsonValue::Null,
+            },
+        );
+
+        for (key, value) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            if let Some(expected) = value {
+                map.insert(
+                    key.into(),
+                    match expected {
+                        Some(x) => JsonValue::String(x.clone()),
+                        None => JsonValue::Null,
+                    },
+                );
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Example {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected example to be a JSON object.")?;
+
+        let index = match json.get("index") {
+            None | Some(JsonValue::Null) => None,
+            Some(v) => Some(
+                v.get::<f64>()
+                    .map(|index| *index as u8)
+                    .ok_or("Expected example.index to be null or a number.")?,
+            ),
+        };
+
+        let parse_part = |key: &str| -> Result<Option<Option<String>>, String> {
+            match json.get(key) {
+                None => Ok(None),
+                Some(JsonValue::Null) => Ok(Some(None)),
+                Some(v) => v
+                    .get::<String>()
+                    .map(|x| Some(Some(x.clone())))
+                    .ok_or_else(|| format!("Expected example.{key} to be null or string.")),
+            }
+        };
+
+        Ok(Example {
+            index,
+            part_1: parse_part("part_1")?,
+            part_2: parse_part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Example, Examples};
+
+    #[test]
+    fn handles_json_examples() {
+        let json = r#"{ "examples": [{ "index": null, "part_1": "142", "part_2": null }, { "index": 2, "part_2": "281" }] }"#.to_string();
+        let examples = Examples::try_from(json).unwrap();
+        assert_eq!(examples.data.len(), 2);
+        assert_eq!(examples.data[0].expected(1), Some(Some("142")));
+        assert_eq!(examples.data[0].expected(2), Some(None));
+        assert_eq!(examples.data[1].expected(1), None);
+        assert_eq!(examples.data[1].file_name("01"), "01_2.txt");
+        assert_eq!(
+            Examples::get_path(Some(2022), "01"),
+            "data/2022/examples/01.json"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_examples() {
+        let json = r#"{ "examples": [{ "index": "a" }] }"#.to_string();
+        Examples::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn roundtrips_examples() {
+        let examples = Examples {
+            data: vec![Example {
+                index: Some(3),
+                part_1: None,
+                part_2: Some(Some("42".into())),
+            }],
+        };
+        let json = tinyjson::JsonValue::from(examples.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Examples::try_from(json).unwrap(), examples);
+    }
+
+    #[test]
+    fn sets_examples_in_index_order() {
+        let mut examples = Examples::default();
+        examples.set(Some(2), 2, Some(Some("281".into())));
+        examples.set(None, 1, Some(Some("142".into())));
+        examples.set(None, 2, Some(None));
+        assert_eq!(examples.data.len(), 2);
+        assert_eq!(examples.data[0].index, None);
+        assert_eq!(examples.data[0].expected(1), Some(Some("142")));
+        assert_eq!(examples.data[0].expected(2), Some(None));
+        assert_eq!(examples.data[1].expected(2), Some(Some("281")));
+    }
+}
@@ -0,0 +1,341 @@
+/// History of benchmark runs, kept in the timings file next to the latest timings of each day.
+use std::{
+    collections::HashMap,
+    env, fs,
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::runner::PartResult;
+use crate::template::timings::{Status, Timing};
+use crate::template::Day;
+
+/// A single benchmark run and the environment it ran in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Run {
+    /// Seconds since the unix epoch, unknown for runs migrated from an older timings file.
+    pub timestamp: Option<u64>,
+    /// Short hash of the git commit that was benched.
+    pub commit: Option<String>,
+    /// Output of `rustc --version`.
+    pub rustc: Option<String>,
+    /// Name of the machine, `AOC_MACHINE_ID` if set, the hostname otherwise.
+    pub machine: Option<String>,
+    pub days: Vec<RunDay>,
+}
+
+/// Benchmark results of a single day within a run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunDay {
+    pub day: Day,
+    pub status: Status,
+    pub part_1: Option<PartSample>,
+    pub part_2: Option<PartSample>,
+}
+
+/// Median duration of a benched part.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartSample {
+    pub median: Duration,
+    /// Number of samples the median was computed from, unknown for migrated runs.
+    pub samples: Option<u64>,
+}
+
+impl Run {
+    /// Records the results of a run along with the current commit, compiler and machine.
+    pub fn capture(days: Vec<RunDay>) -> Self {
+        Run {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|d| d.as_secs()),
+            commit: command_output("git", &["rev-parse", "--short", "HEAD"]),
+            rustc: command_output("rustc", &["--version"]),
+            machine: machine_id(),
+            days,
+        }
+    }
+
+    /// Converts the timings of a file without history into a run with unknown environment.
+    pub fn migrate(timings: &[Timing]) -> Self {
+        Run {
+            timestamp: None,
+            commit: None,
+            rustc: None,
+            machine: None,
+            days: timings.iter().map(RunDay::from).collect(),
+        }
+    }
+}
+
+impl RunDay {
+    /// Collects the benched parts of a day, un-benched parts and parts without an answer are left out.
+    pub fn new(day: Day, status: Status, results: &[PartResult]) -> Self {
+        let mut run_day = RunDay {
+            day,
+            status,
+            part_1: None,
+            part_2: None,
+        };
+
+        for result in results
+            .iter()
+            .filter(|result| result.answer.is_some() && result.stats.samples > 1)
+        {
+            let sample = Some(PartSample {
+                median: result.stats.median,
+                samples: u64::try_from(result.stats.samples).ok(),
+            });
+
+            match result.part {
+                1 => run_day.part_1 = sample,
+                2 => run_day.part_2 = sample,
+                _ => {}
+            }
+        }
+
+        run_day
+    }
+}
+
+impl From<&Timing> for RunDay {
+    fn from(timing: &Timing) -> Self {
+        let sample = |part| {
+            timing.part(part).map(|median| PartSample {
+                median,
+                samples: None,
+            })
+        };
+
+        RunDay {
+            day: timing.day,
+            status: timing.status,
+            part_1: sample(1),
+            part_2: sample(2),
+        }
+    }
+}
+
+/// Runs a command and returns its trimmed output, `None` if it failed.
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    let stdout = String::from_utf8(output.stdout).ok()?;
+    Some(stdout.trim().to_string()).filter(|s| output.status.success() && !s.is_empty())
+}
+
+fn machine_id() -> Option<String> {
+    env::var("AOC_MACHINE_ID")
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .or_else(|| command_output("hostname", &[]))
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn string_or_null(value: Option<&String>) -> JsonValue {
+    value.map_or(JsonValue::Null, |s| JsonValue::String(s.clone()))
+}
+
+fn optional_string(json: &HashMap<String, JsonValue>, key: &str) -> Option<String> {
+    json.get(key).and_then(|v| v.get::<String>()).cloned()
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn optional_number(json: &HashMap<String, JsonValue>, key: &str) -> Option<u64> {
+    json.get(key)
+        .and_then(|v| v.get::<f64>())
+        .map(|n| *n as u64)
+}
+
+impl From<&Run> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            value
+                .timestamp
+                .map_or(JsonValue::Null, |t| JsonValue::Number(t as f64)),
+        );
+        map.insert("commit".into(), string_or_null(value.commit.as_ref()));
+        map.insert("rustc".into(), string_or_null(value.rustc.as_ref()));
+        map.insert("machine".into(), string_or_null(value.machine.as_ref()));
+        map.insert(
+            "days".into(),
+            JsonValue::Array(value.days.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run to be a JSON object.")?;
+
+        let days = json
+            .get("days")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected run.days to be an array.")?
+            .iter()
+            .map(RunDay::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Run {
+            timestamp: optional_number(json, "timestamp"),
+            commit: optional_string(json, "commit"),
+            rustc: optional_string(json, "rustc"),
+            machine: optional_string(json, "machine"),
+            days,
+        })
+    }
+}
+
+impl From<&RunDay> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &RunDay) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+
+        for (key, sample) in [("part_1", &value.part_1), ("part_2", &value.part_2)] {
+            let json = match sample {
+                Some(sample) => {
+                    let mut part: HashMap<String, JsonValue> = HashMap::new();
+                    part.insert(
+                        "nanos".into(),
+                        JsonValue::Number(sample.median.as_nanos() as f64),
+                    );
+                    part.insert(
+                        "samples".into(),
+                        sample
+                            .samples
+                            .map_or(JsonValue::Null, |s| JsonValue::Number(s as f64)),
+                    );
+                    JsonValue::Object(part)
+                }
+                None => JsonValue::Null,
+            };
+            map.insert(key.into(), json);
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for RunDay {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected run day to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected run.day to be a Day struct.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .and_then(|s| Status::from_str(s).ok())
+            .ok_or("Expected run.status to be a status string.")?;
+
+        let sample = |key: &str| -> Result<Option<PartSample>, String> {
+            match json.get(key) {
+                None => Ok(None),
+                Some(v) if v.is_null() => Ok(None),
+                Some(v) => {
+                    let part = v
+                        .get::<HashMap<String, JsonValue>>()
+                        .ok_or(format!("Expected run.{key} to be null or an object."))?;
+                    let nanos = optional_number(part, "nanos")
+                        .ok_or(format!("Expected run.{key}.nanos to be a number."))?;
+
+                    Ok(Some(PartSample {
+                        median: Duration::from_nanos(nanos),
+                        samples: optional_number(part, "samples"),
+                    }))
+                }
+            }
+        };
+
+        Ok(RunDay {
+            day,
+            status,
+            part_1: sample("part_1")?,
+            part_2: sample("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use tinyjson::JsonValue;
+
+    use super::{PartSample, Run, RunDay};
+    use crate::day;
+    use crate::template::timings::{Status, Timing};
+
+    #[test]
+    fn serializes_runs() {
+        let run = Run {
+            timestamp: Some(1_701_388_800),
+            commit: Some("1a2b3c4".into()),
+            rustc: Some("rustc 1.83.0".into()),
+            machine: None,
+            days: vec![RunDay {
+                day: day!(1),
+                status: Status::Ok,
+                part_1: Some(PartSample {
+                    median: Duration::from_nanos(1500),
+                    samples: Some(100),
+                }),
+                part_2: None,
+            }],
+        };
+
+        let json = JsonValue::from(&run).stringify().unwrap();
+        let parsed = Run::try_from(&json.parse::<JsonValue>().unwrap()).unwrap();
+        assert_eq!(parsed, run);
+    }
+
+    #[test]
+    fn migrates_timings() {
+        let timing = Timing::ok(day!(2), Some(Duration::from_nanos(1500)), None);
+
+        let run = Run::migrate(&[timing]);
+        assert_eq!(run.timestamp, None);
+        assert_eq!(
+            run.days,
+            vec![RunDay {
+                day: day!(2),
+                status: Status::Ok,
+                part_1: Some(PartSample {
+                    median: Duration::from_nanos(1500),
+                    samples: None,
+                }),
+                part_2: None,
+            }]
+        );
+    }
+}
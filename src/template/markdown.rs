@@ -0,0 +1,370 @@
+/// Renders the markdown of puzzle descriptions for the terminal.
+use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RESET};
+
+/// Whether a puzzle description contains the second part.
+pub fn has_part_two(markdown: &str) -> bool {
+    markdown.contains("--- Part Two ---")
+}
+
+/// Renders headings, code blocks, lists, emphasis, inline code and links with ANSI styling.
+pub fn render(markdown: &str) -> String {
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut output: Vec<String> = vec![];
+    let mut in_code_block = false;
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        i += 1;
+
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            output.push(if line.is_empty() {
+                String::new()
+            } else {
+                format!("    {line}")
+            });
+            continue;
+        }
+
+        // setext headings are underlined with a line of `-` or `=`.
+        if !line.trim().is_empty() && lines.get(i).is_some_and(|next| is_underline(next)) {
+            i += 1;
+            output.push(heading(line));
+            continue;
+        }
+
+        if let Some(text) = line.trim_start_matches('#').strip_prefix(' ') {
+            if line.starts_with('#') {
+                output.push(heading(text));
+                continue;
+            }
+        }
+
+        if is_underline(line) {
+            output.push("-".repeat(line.trim().len()));
+            continue;
+        }
+
+        let indent = line.len() - line.trim_start().len();
+        match line.trim_start().strip_prefix("* ") {
+            Some(item) => output.push(format!("{}  • {}", &line[..indent], render_inline(item))),
+            None => output.push(render_inline(line)),
+        }
+    }
+
+    let mut rendered = output.join("\n");
+    rendered.push('\n');
+    rendered
+}
+
+fn is_underline(line: &str) -> bool {
+    let line = line.trim();
+    line.len() >= 3 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='))
+}
+
+fn heading(text: &str) -> String {
+    format!(
+        "{ANSI_BOLD}{ANSI_GREEN}{}{ANSI_RESET}",
+        unescape(text.trim())
+    )
+}
+
+fn unescape(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut chars = text.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => output.extend(chars.next()),
+            c => output.push(c),
+        }
+    }
+
+    output
+}
+
+/// Renders emphasis and strong emphasis in bold, inline code verbatim and links as their text.
+fn render_inline(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    let mut is_bold = false;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+
+        match c {
+            '\\' => {
+                if let Some(escaped) = rest.chars().next() {
+                    output.push(escaped);
+                    rest = &rest[escaped.len_utf8()..];
+                }
+            }
+            '`' => match rest.find('`') {
+                Some(end) => {
+                    // emphasis inside of code is how puzzles highlight answers, e.g. `*142*`.
+                    output.push_str(&render_inline_code(&rest[..end], is_bold));
+                    rest = &rest[end + 1..];
+                }
+                None => output.push(c),
+            },
+            '*' => {
+                rest = rest.strip_prefix('*').unwrap_or(rest);
+                is_bold = !is_bold;
+                output.push_str(if is_bold { ANSI_BOLD } else { ANSI_RESET });
+            }
+            '[' => match parse_link(rest) {
+                Some((link_text, len)) => {
+                    output.push_str(&render_inline(link_text));
+                    rest = &rest[len..];
+                }
+                None => output.push(c),
+            },
+            c => output.push(c),
+        }
+    }
+
+    if is_bold {
+        output.push_str(ANSI_RESET);
+    }
+
+    output
+}
+
+fn render_inline_code(code: &str, is_bold: bool) -> String {
+    match code.strip_prefix('*').and_then(|c| c.strip_suffix('*')) {
+        Some(code) if !is_bold => format!("{ANSI_BOLD}{code}{ANSI_RESET}"),
+        Some(code) => code.to_string(),
+        None => code.to_string(),
+    }
+}
+
+/// Parses the remainder of a link like `text](url)`, returns the text and the length of the link.
+fn parse_link(rest: &str) -> Option<(&str, usize)> {
+    let text_end = rest.find("](")?;
+    let url_end = text_end + rest[text_end..].find(')')?;
+    Some((&rest[..text_end], url_end + 1))
+}
+
+/// The example input and expected answer found in the description of a part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartExample {
+    pub part: u8,
+    pub input: Option<String>,
+    pub answer: Option<String>,
+}
+
+/// Extracts the example of every part in a puzzle description.
+///
+/// The example input is the first code block that follows a paragraph mentioning an example,
+/// falling back to the first code block of the part. The expected answer is the last emphasized
+/// inline code of the part, which is how puzzles highlight the answer for the example.
+pub fn extract_examples(markdown: &str) -> Vec<PartExample> {
+    let (part_one, part_two) = match markdown.find("--- Part Two ---") {
+        Some(index) => {
+            let start = markdown[..index].rfind('\n').map_or(0, |i| i + 1);
+            (&markdown[..start], Some(&markdown[start..]))
+        }
+        None => (markdown, None),
+    };
+
+    [Some(part_one), part_two]
+        .into_iter()
+        .flatten()
+        .zip(1..)
+        .map(|(section, part)| PartExample {
+            part,
+            input: find_example_block(section),
+            answer: emphasized_code(section).last().map(ToString::to_string),
+        })
+        .collect()
+}
+
+/// Returns the code blocks of a section, each with the paragraph that precedes it.
+fn code_blocks(section: &str) -> Vec<(String, String)> {
+    let mut blocks = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut block: Option<Vec<&str>> = None;
+
+    for line in section.lines() {
+        match (&mut block, line.trim_start().starts_with("```")) {
+            (None, true) => block = Some(vec![]),
+            (Some(lines), true) => {
+                let mut code = lines.join("\n").trim_end_matches('\n').to_string();
+                code.push('\n');
+                blocks.push((paragraph.join(" "), code));
+                paragraph.clear();
+                block = None;
+            }
+            (Some(lines), false) => lines.push(line),
+            (None, false) if line.trim().is_empty() => {}
+            (None, false) => {
+                paragraph.clear();
+                paragraph.push(line);
+            }
+        }
+    }
+
+    blocks
+}
+
+fn find_example_block(section: &str) -> Option<String> {
+    let blocks = code_blocks(section);
+
+    blocks
+        .iter()
+        .find(|(paragraph, _)| paragraph.to_lowercase().contains("example"))
+        .or(blocks.first())
+        .map(|(_, code)| code.clone())
+}
+
+/// Returns the emphasized inline code of a section, written as `` `*x*` `` or `` *`x`* ``.
+fn emphasized_code(section: &str) -> Vec<&str> {
+    let mut found = vec![];
+    let mut in_code_block = false;
+
+    for line in section.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            continue;
+        }
+
+        let mut rest = line;
+
+        while let Some(start) = rest.find('`') {
+            let Some(end) = rest[start + 1..].find('`').map(|i| start + 1 + i) else {
+                break;
+            };
+
+            let code = &rest[start + 1..end];
+            let is_wrapped = rest[..start].ends_with('*') && rest[end + 1..].starts_with('*');
+
+            match code.strip_prefix('*').and_then(|c| c.strip_suffix('*')) {
+                Some(code) => found.push(code),
+                None if is_wrapped => found.push(code),
+                None => {}
+            }
+
+            rest = &rest[end + 1..];
+        }
+    }
+
+    found
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_examples, has_part_two, render, PartExample};
+    use crate::template::{ANSI_BOLD, ANSI_GREEN, ANSI_RESET};
+
+    #[test]
+    fn renders_headings() {
+        let rendered = render("\\--- Day 1: Trebuchet?! ---\n----------\n\n## Title");
+        assert_eq!(
+            rendered,
+            format!("{ANSI_BOLD}{ANSI_GREEN}--- Day 1: Trebuchet?! ---{ANSI_RESET}\n\n{ANSI_BOLD}{ANSI_GREEN}Title{ANSI_RESET}\n")
+        );
+    }
+
+    #[test]
+    fn renders_code_blocks_verbatim() {
+        let rendered = render("Example:\n\n```\n1abc2\n*a*b\n\n```\n");
+        assert_eq!(rendered, "Example:\n\n    1abc2\n    *a*b\n\n");
+    }
+
+    #[test]
+    fn renders_inline_styles() {
+        let rendered = render(
+            "Something is *wrong* with [global snow](/2023/events), `12` and `*142*`, 2 \\* 3.",
+        );
+        assert_eq!(
+            rendered,
+            format!("Something is {ANSI_BOLD}wrong{ANSI_RESET} with global snow, 12 and {ANSI_BOLD}142{ANSI_RESET}, 2 * 3.\n")
+        );
+    }
+
+    #[test]
+    fn renders_lists() {
+        let rendered = render("* one\n* **two**");
+        assert_eq!(
+            rendered,
+            format!("  • one\n  • {ANSI_BOLD}two{ANSI_RESET}\n")
+        );
+    }
+
+    #[test]
+    fn detects_part_two() {
+        assert!(has_part_two("\\--- Part Two ---\n----------"));
+        assert!(!has_part_two("\\--- Day 1: Trebuchet?! ---\n----------"));
+    }
+
+    #[test]
+    fn extracts_examples_of_both_parts() {
+        let markdown = [
+            "\\--- Day 1: Trebuchet?! ---",
+            "----------",
+            "",
+            "The newly-improved calibration document consists of lines of text.",
+            "",
+            "```",
+            "not an example",
+            "```",
+            "",
+            "For example:",
+            "",
+            "```",
+            "1abc2",
+            "a1b2c3d4e5f",
+            "",
+            "```",
+            "",
+            "Adding these together produces `*142*`, not `12`.",
+            "",
+            "\\--- Part Two ---",
+            "----------",
+            "",
+            "For example:",
+            "",
+            "```",
+            "two1nine",
+            "```",
+            "",
+            "Values are `29` and *`83`*, adding these produces `*281*`.",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            extract_examples(&markdown),
+            vec![
+                PartExample {
+                    part: 1,
+                    input: Some("1abc2\na1b2c3d4e5f\n".into()),
+                    answer: Some("142".into()),
+                },
+                PartExample {
+                    part: 2,
+                    input: Some("two1nine\n".into()),
+                    answer: Some("281".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn extracts_examples_without_answers() {
+        let examples = extract_examples("Some text.\n\n```\n1 2 3\n```\n");
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].input.as_deref(), Some("1 2 3\n"));
+        assert_eq!(examples[0].answer, None);
+    }
+}
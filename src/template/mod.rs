@@ -1,33 +1,52 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
 pub mod runner;
 
 pub use day::*;
+pub use error::Error;
+pub use puzzle::*;
+pub use report::ReportFormat;
+pub use templates::{DEFAULT_ANSWER_TYPE, DEFAULT_TEMPLATE};
 
+mod answers;
+mod dashboard;
 mod day;
+mod error;
+mod examples;
+mod history;
+mod markdown;
+mod puzzle;
 mod readme_benchmarks;
+mod readme_stars;
+mod report;
 mod run_multi;
+mod submissions;
+mod templates;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 
 /// Helper function that reads a text file to a string.
+/// Accepts a [`Day`] of the default season or a [`Puzzle`] of any season.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    read_file_indexed(folder, day, None)
+pub fn read_file(folder: &str, puzzle: impl Into<Puzzle>) -> String {
+    read_file_indexed(folder, puzzle, None)
 }
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file_indexed(folder: &str, day: Day, index: Option<u8>) -> String {
+pub fn read_file_indexed(folder: &str, puzzle: impl Into<Puzzle>, index: Option<u8>) -> String {
+    let Puzzle { year, day } = puzzle.into();
     let cwd = env::current_dir().unwrap();
     let suffix = index.map_or(String::new(), |i| format!("_{i}"));
     let filepath = cwd
-        .join("data")
+        .join(data_dir(year))
         .join(folder)
         .join(format!("{day}{suffix}.txt"));
     let f = fs::read_to_string(filepath);
@@ -36,19 +55,23 @@ pub fn read_file_indexed(folder: &str, day: Day, index: Option<u8>) -> String {
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: impl Into<Puzzle>, part: u8) -> String {
+    let Puzzle { year, day } = puzzle.into();
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(year))
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY` and `PUZZLE` and sets up the input and runner for each part.
+/// The season of `PUZZLE` is derived from the name of the binary, e.g. `2022_01` for day 1 of 2022.
+/// In test builds, it also adds a test for each example declared in the day's manifest.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The generated `main` accepts the options of [`runner::RunOptions`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -65,14 +88,47 @@ macro_rules! solution {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
+        /// The current puzzle.
+        const PUZZLE: $crate::template::Puzzle =
+            $crate::template::Puzzle::__from_module_path(module_path!(), DAY);
+
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let result = RunOptions::from_env().and_then(|options| {
+                let input = options.read_input(PUZZLE)?;
+                $(
+                    if options.runs_part($part) {
+                        run_part($func, input.as_str(), PUZZLE, $part, &options)?;
+                    }
+                )*
+                Ok(())
+            });
+
+            if let Err(e) = result {
+                eprintln!("{e}");
+                std::process::exit(e.exit_code());
+            }
+        }
+
+        /// Runs the solution without printing. Used when the solution is compiled into the main binary.
+        #[doc(hidden)]
+        #[allow(dead_code)]
+        pub fn __run_in_process(
+            input: &str,
+            is_timed: bool,
+        ) -> Vec<$crate::template::runner::PartResult> {
+            use $crate::template::runner::*;
+            vec![$( solve_part($func, input, DAY, $part, is_timed), )*]
+        }
+
+        /// Tests for the examples declared in `data/examples/NN.json`, generated by `build.rs`.
+        #[cfg(test)]
+        mod example_tests {
+            include!(concat!(env!("OUT_DIR"), "/examples_", env!("CARGO_BIN_NAME"), ".rs"));
         }
     };
 }
@@ -0,0 +1,203 @@
+use std::env;
+use std::fmt::Display;
+
+#[cfg(feature = "today")]
+use chrono::Datelike;
+
+use crate::template::Day;
+
+/// Identifies a puzzle by its season and day.
+///
+/// Puzzles of the default season, configured with `AOC_YEAR`, have no year and keep their files at the top level,
+/// i.e. `src/bin/01.rs` and `data/inputs/01.txt`. Puzzles of other seasons keep their files in their own directories,
+/// i.e. `src/bin/2022_01.rs` and `data/2022/inputs/01.txt`.
+///
+/// # Display
+/// This value displays as a two digit day number, followed by the year for other seasons.
+///
+/// ```
+/// # use advent_of_code::template::{Day, Puzzle};
+/// let puzzle = Puzzle { year: Some(2022), day: Day::new(8).unwrap() };
+/// assert_eq!(puzzle.to_string(), "08 (2022)")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Puzzle {
+    pub year: Option<u16>,
+    pub day: Day,
+}
+
+impl Puzzle {
+    /// Creates a [`Puzzle`], the year is dropped if it is the default season.
+    pub fn new(year: Option<u16>, day: Day) -> Self {
+        Self {
+            year: season(year),
+            day,
+        }
+    }
+
+    // Not part of the public API, used by `solution!` to derive the season from the module path.
+    // It ends with the binary name, e.g. `2022_01`, both in a solution binary and in the registry of the main binary.
+    #[doc(hidden)]
+    pub const fn __from_module_path(module_path: &str, day: Day) -> Self {
+        let path = module_path.as_bytes();
+        let year = if path.len() < 7 {
+            None
+        } else {
+            parse_bin_year(path.split_at(path.len() - 7).1)
+        };
+        Self { year, day }
+    }
+
+    /// Name of the solution binary, e.g. `01` or `2022_01`.
+    #[must_use]
+    pub fn bin_name(&self) -> String {
+        match self.year {
+            Some(year) => format!("{year}_{}", self.day),
+            None => self.day.to_string(),
+        }
+    }
+
+    #[must_use]
+    pub fn bin_path(&self) -> String {
+        format!("./src/bin/{}.rs", self.bin_name())
+    }
+
+    #[must_use]
+    pub fn input_path(&self) -> String {
+        format!("{}/inputs/{}.txt", data_dir(self.year), self.day)
+    }
+
+    #[must_use]
+    pub fn example_path(&self) -> String {
+        self.indexed_example_path(None)
+    }
+
+    /// Path of an example file, e.g. `data/examples/01_2.txt` for the example with index 2.
+    #[must_use]
+    pub fn indexed_example_path(&self, index: Option<u8>) -> String {
+        let suffix = index.map_or(String::new(), |i| format!("_{i}"));
+        format!("{}/examples/{}{suffix}.txt", data_dir(self.year), self.day)
+    }
+
+    #[must_use]
+    pub fn puzzle_path(&self) -> String {
+        format!("{}/puzzles/{}.md", data_dir(self.year), self.day)
+    }
+
+    /// The year of the puzzle, resolving the default season.
+    #[must_use]
+    pub fn resolved_year(&self) -> Option<u16> {
+        self.year.or_else(default_year)
+    }
+}
+
+#[cfg(feature = "today")]
+impl Puzzle {
+    /// Returns today's puzzle if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Unless a season is given, the puzzle belongs to the current year.
+    pub fn today(year: Option<u16>) -> Option<Self> {
+        let day = Day::today()?;
+        let year = year.or_else(|| u16::try_from(chrono::Utc::now().year()).ok());
+        Some(Self::new(year, day))
+    }
+}
+
+impl From<Day> for Puzzle {
+    /// A day of the default season.
+    fn from(day: Day) -> Self {
+        Self { year: None, day }
+    }
+}
+
+impl Display for Puzzle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{} ({year})", self.day),
+            None => write!(f, "{}", self.day),
+        }
+    }
+}
+
+/// The default season, configured with the `AOC_YEAR` environment variable.
+#[must_use]
+pub fn default_year() -> Option<u16> {
+    env::var("AOC_YEAR").ok().and_then(|x| x.parse().ok())
+}
+
+/// Returns the season of `year`, i.e. `None` for the default season.
+#[must_use]
+pub fn season(year: Option<u16>) -> Option<u16> {
+    year.filter(|year| Some(*year) != default_year())
+}
+
+/// Directory that holds the data files of a season, e.g. `data` or `data/2022`.
+#[must_use]
+pub fn data_dir(year: Option<u16>) -> String {
+    match year {
+        Some(year) => format!("data/{year}"),
+        None => "data".into(),
+    }
+}
+
+/// Parses the year of a binary name like `2022_01`.
+const fn parse_bin_year(name: &[u8]) -> Option<u16> {
+    if name.len() != 7 || name[4] != b'_' {
+        return None;
+    }
+
+    let mut year = 0;
+    let mut i = 0;
+
+    while i < 4 {
+        if !name[i].is_ascii_digit() {
+            return None;
+        }
+        year = year * 10 + (name[i] - b'0') as u16;
+        i += 1;
+    }
+
+    Some(year)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Puzzle;
+    use crate::day;
+
+    #[test]
+    fn derives_year_from_module_path() {
+        let puzzle = Puzzle::__from_module_path("2022_05", day!(5));
+        assert_eq!(puzzle.year, Some(2022));
+        assert_eq!(Puzzle::__from_module_path("05", day!(5)).year, None);
+        assert_eq!(
+            Puzzle::__from_module_path("advent_of_code::solutions::day_2022_05", day!(5)).year,
+            Some(2022)
+        );
+        assert_eq!(
+            Puzzle::__from_module_path("advent_of_code::solutions::day_05", day!(5)).year,
+            None
+        );
+    }
+
+    #[test]
+    fn builds_paths_for_default_season() {
+        let puzzle = Puzzle::from(day!(1));
+        assert_eq!(puzzle.bin_path(), "./src/bin/01.rs");
+        assert_eq!(puzzle.input_path(), "data/inputs/01.txt");
+        assert_eq!(puzzle.puzzle_path(), "data/puzzles/01.md");
+    }
+
+    #[test]
+    fn builds_paths_for_other_seasons() {
+        let puzzle = Puzzle {
+            year: Some(2022),
+            day: day!(1),
+        };
+        assert_eq!(puzzle.bin_name(), "2022_01");
+        assert_eq!(puzzle.bin_path(), "./src/bin/2022_01.rs");
+        assert_eq!(puzzle.input_path(), "data/2022/inputs/01.txt");
+        assert_eq!(puzzle.example_path(), "data/2022/examples/01.txt");
+    }
+}
@@ -1,18 +1,43 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{cmp::Reverse, collections::HashMap, fmt::Display, fs, io, time::Duration};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::dashboard::{
+    collect_details, format_date, Column, Dashboard, DayDetails, Sort,
+};
+use crate::template::readme_stars;
+use crate::template::runner::{format_bytes, MemoryStats};
+use crate::template::timings::{Status, Timing, Timings};
+use crate::template::{Day, Puzzle};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Marker that encloses the table of a season. Other seasons than the default one have their own table.
+fn marker(year: Option<u16>) -> String {
+    match year {
+        Some(year) => format!("<!--- benchmarking table {year} --->"),
+        None => MARKER.into(),
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     Parser(String),
+    /// A data file of the season, e.g. the answers or submissions, could not be parsed.
+    Data(String),
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "{e}"),
+            Error::Data(e) => write!(f, "Could not parse {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -24,17 +49,12 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
+            "too many occurences of marker in README.".into(),
         ));
     }
 
@@ -51,78 +71,300 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+/// Formats a part's median, followed by its spread if it was benched with more than one sample.
+/// Parts without timings show why the day did not complete.
+fn format_part(median: Option<Duration>, spread: Option<Duration>, status: Status) -> String {
+    match (median, spread) {
+        (Some(median), Some(spread)) => format!("`{median:.1?} ± {spread:.1?}`"),
+        (Some(median), None) => format!("`{median:.1?}`"),
+        (None, _) => match status {
+            Status::Ok => "`-`".into(),
+            Status::Unsolved => "✖ unsolved".into(),
+            Status::Panic => "💥 panic".into(),
+            Status::Timeout => "⏱ timeout".into(),
+            Status::Error => "⚠ error".into(),
+        },
+    }
+}
+
+/// Formats the peak heap usage and number of allocations of a part.
+fn format_memory(memory: Option<MemoryStats>) -> String {
+    match memory {
+        Some(memory) => format!(
+            "`{}` / {} allocs",
+            format_bytes(memory.peak_bytes),
+            memory.allocations
+        ),
+        None => "`-`".into(),
+    }
+}
+
+/// A row of the table, days that only have stars have no timing.
+struct Row<'a> {
+    day: Day,
+    timing: Option<&'a Timing>,
+    details: DayDetails,
+}
+
+/// Formats the cells of a column for a row.
+fn format_cells(column: Column, row: &Row, total: Duration) -> Vec<String> {
+    let timing = row.timing;
+    let status = timing.map_or(Status::Ok, |t| t.status);
+
+    match column {
+        Column::Stars => vec!["⭐".repeat(row.details.stars())],
+        Column::Part1 => vec![format_part(
+            timing.and_then(|t| t.part_1),
+            timing.and_then(|t| t.part_1_spread),
+            status,
+        )],
+        Column::Part2 => vec![format_part(
+            timing.and_then(|t| t.part_2),
+            timing.and_then(|t| t.part_2_spread),
+            status,
+        )],
+        Column::Memory => vec![
+            format_memory(timing.and_then(|t| t.part_1_memory)),
+            format_memory(timing.and_then(|t| t.part_2_memory)),
+        ],
+        Column::Lines => vec![row
+            .details
+            .lines
+            .map_or("`-`".into(), |lines| lines.to_string())],
+        Column::Solved => vec![row.details.solved.map_or("`-`".into(), format_date)],
+        Column::Share => vec![match timing {
+            Some(timing) if !total.is_zero() => format!(
+                "{:.1}%",
+                timing.total.as_secs_f64() / total.as_secs_f64() * 100_f64
+            ),
+            _ => "`-`".into(),
+        }],
+    }
+}
+
+/// Collects the rows of the table in the configured order. Days without timings are only shown
+/// if the table has a stars column and they have at least one star.
+fn collect_rows<'a>(
+    timings: &'a Timings,
+    dashboard: &Dashboard,
+    details: &HashMap<Day, DayDetails>,
+) -> Vec<Row<'a>> {
+    let mut rows: Vec<Row> = timings
+        .data
+        .iter()
+        .map(|timing| Row {
+            day: timing.day,
+            timing: Some(timing),
+            details: details.get(&timing.day).copied().unwrap_or_default(),
+        })
+        .collect();
+
+    if dashboard.shows(Column::Stars) {
+        rows.extend(
+            details
+                .iter()
+                .filter(|(day, details)| {
+                    details.stars() > 0 && !timings.data.iter().any(|t| t.day == **day)
+                })
+                .map(|(day, details)| Row {
+                    day: *day,
+                    timing: None,
+                    details: *details,
+                }),
+        );
+    }
+
+    rows.sort_by_key(|row| row.day);
+
+    // days without timings come last in either order.
+    match dashboard.sort {
+        Sort::Day => {}
+        Sort::Slowest => rows.sort_by_key(|row| Reverse(row.timing.map(|t| t.total))),
+        Sort::Fastest => {
+            rows.sort_by_key(|row| (row.timing.is_none(), row.timing.map(|t| t.total)))
+        }
+    }
+
+    rows
+}
+
+/// Returns the slowest days by total time, up to `count`.
+fn slowest_days(timings: &Timings, count: usize) -> Vec<Day> {
+    let mut slowest: Vec<&Timing> = timings.data.iter().filter(|t| !t.total.is_zero()).collect();
+    slowest.sort_by_key(|t| Reverse(t.total));
+    slowest.iter().take(count).map(|t| t.day).collect()
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: &Timings,
+    total_millis: f64,
+    year: Option<u16>,
+    dashboard: &Dashboard,
+    details: &HashMap<Day, DayDetails>,
+) -> String {
+    let header = match (&dashboard.title, year) {
+        (Some(title), _) => format!("{prefix} {title}"),
+        (None, Some(year)) => format!("{prefix} {year} Benchmarks"),
+        (None, None) => format!("{prefix} Benchmarks"),
+    };
+    let marker = marker(year);
+
+    // memory columns are only shown if heap usage was recorded for at least one part.
+    let has_memory = timings
+        .data
+        .iter()
+        .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some());
+
+    let columns: Vec<Column> = dashboard
+        .columns
+        .iter()
+        .copied()
+        .filter(|column| *column != Column::Memory || has_memory)
+        .collect();
+
+    let mut headers = vec!["Day"];
+    headers.extend(columns.iter().flat_map(|column| column.headers()));
+
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+    lines.push(format!("| {} |", headers.join(" | ")));
+    lines.push(format!("|{}", " :---: |".repeat(headers.len())));
+
+    let total = timings.total();
+    let highlighted = slowest_days(timings, dashboard.highlight_slowest);
+
+    for row in collect_rows(timings, dashboard, details) {
+        let path = Puzzle { year, day: row.day }.bin_path();
+        let day = format!("[Day {}]({})", row.day.into_inner(), path);
+
+        let mut cells = vec![if highlighted.contains(&row.day) {
+            format!("**{day}**")
+        } else {
+            day
+        }];
+        cells.extend(
+            columns
+                .iter()
+                .flat_map(|column| format_cells(*column, &row, total)),
+        );
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    if dashboard.total {
+        lines.push(String::new());
+        lines.push(format!("**Total: {total_millis:.2}ms**"));
+    }
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+fn update_content(
+    s: &mut String,
+    timings: &Timings,
+    total_millis: f64,
+    year: Option<u16>,
+    dashboard: &Dashboard,
+    details: &HashMap<Day, DayDetails>,
+) -> Result<(), Error> {
+    let table = construct_table("##", timings, total_millis, year, dashboard, details);
+    replace_table(s, &table, &marker(year), year)?;
+
+    // the stars column replaces the stars table, which would otherwise show the stars twice.
+    if dashboard.shows(Column::Stars) {
+        remove_table(s, &readme_stars::marker(year))?;
+    }
+
+    Ok(())
+}
+
+/// Replaces the table enclosed by `marker`, including the markers.
+/// The table of another season is added to the end of the readme when it is stored for the first time.
+pub fn replace_table(
+    s: &mut String,
+    table: &str,
+    marker: &str,
+    year: Option<u16>,
+) -> Result<(), Error> {
+    if year.is_some() && !s.contains(marker) {
+        s.push_str(&format!("\n{table}\n"));
+        return Ok(());
+    }
+
+    let positions = locate_table(s, marker)?;
+    s.replace_range(positions.pos_start..positions.pos_end, table);
+    Ok(())
+}
+
+/// Removes the table enclosed by `marker`, including the markers and the blank line after it.
+/// A readme without the marker is left as is.
+pub fn remove_table(s: &mut String, marker: &str) -> Result<(), Error> {
+    if !s.contains(marker) {
+        return Ok(());
+    }
+
+    let positions = locate_table(s, marker)?;
+    let newlines = s[positions.pos_end..]
+        .chars()
+        .take(2)
+        .take_while(|c| *c == '\n')
+        .count();
+    s.replace_range(positions.pos_start..positions.pos_end + newlines, "");
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Regenerates the benchmarks table of a season, laid out as configured in the readme config file.
+pub fn update(timings: Timings, year: Option<u16>) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(
+        &mut readme,
+        &timings,
+        total_millis,
+        year,
+        &Dashboard::read_from_file(),
+        &collect_details(year)?,
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::{collections::HashMap, time::Duration};
+
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day,
+        template::{
+            dashboard::{Column, Dashboard, DayDetails, Sort},
+            runner::MemoryStats,
+            timings::{Status, Timing, Timings},
+        },
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
-                },
+                Timing::ok(
+                    day!(1),
+                    Some(Duration::from_millis(10)),
+                    Some(Duration::from_millis(20)),
+                ),
+                Timing::ok(
+                    day!(2),
+                    Some(Duration::from_millis(30)),
+                    Some(Duration::from_millis(40)),
+                ),
+                Timing::ok(
+                    day!(4),
+                    Some(Duration::from_millis(40)),
+                    Some(Duration::from_millis(50)),
+                ),
             ],
+            history: vec![],
         }
     }
 
@@ -130,28 +372,68 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            None,
+            &Dashboard::default(),
+            &HashMap::new(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            None,
+            &Dashboard::default(),
+            &HashMap::new(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            None,
+            &Dashboard::default(),
+            &HashMap::new(),
+        )
+        .unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            None,
+            &Dashboard::default(),
+            &HashMap::new(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            None,
+            &Dashboard::default(),
+            &HashMap::new(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -159,7 +441,15 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            None,
+            &Dashboard::default(),
+            &HashMap::new(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -167,10 +457,10 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -179,4 +469,202 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_spread() {
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_spread = Some(Duration::from_micros(1200));
+        update_content(
+            &mut s,
+            &timings,
+            190.0,
+            None,
+            &Dashboard::default(),
+            &HashMap::new(),
+        )
+        .unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms ± 1.2ms` | `20.0ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_status() {
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].status = Status::Timeout;
+        update_content(
+            &mut s,
+            &timings,
+            190.0,
+            None,
+            &Dashboard::default(),
+            &HashMap::new(),
+        )
+        .unwrap();
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | `40.0ms` | ⏱ timeout |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(MemoryStats {
+            peak_bytes: 2048,
+            total_bytes: 4096,
+            allocations: 12,
+        });
+        update_content(
+            &mut s,
+            &timings,
+            190.0,
+            None,
+            &Dashboard::default(),
+            &HashMap::new(),
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 Memory | Part 2 Memory |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` | `2.0 KiB` / 12 allocs | `-` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` | `-` | `-` |"));
+    }
+
+    #[test]
+    fn adds_table_for_other_seasons() {
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            Some(2022),
+            &Dashboard::default(),
+            &HashMap::new(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            Some(2022),
+            &Dashboard::default(),
+            &HashMap::new(),
+        )
+        .unwrap();
+        assert_eq!(s.matches("<!--- benchmarking table 2022 --->").count(), 2);
+        assert_eq!(s.matches("## 2022 Benchmarks").count(), 1);
+        assert!(s.contains("| [Day 1](./src/bin/2022_01.rs) | `10.0ms` | `20.0ms` |"));
+        assert!(s.starts_with(&format!("foo\n{}{}", MARKER, MARKER)));
+    }
+
+    #[test]
+    fn format_configured_dashboard() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        let dashboard = Dashboard {
+            title: Some("Dashboard".into()),
+            columns: vec![Column::Stars, Column::Part2, Column::Lines, Column::Share],
+            sort: Sort::Slowest,
+            highlight_slowest: 1,
+            total: false,
+        };
+        let details = HashMap::from([
+            (
+                day!(1),
+                DayDetails {
+                    part_1_star: true,
+                    part_2_star: true,
+                    lines: Some(42),
+                    solved: None,
+                },
+            ),
+            (
+                day!(5),
+                DayDetails {
+                    part_1_star: true,
+                    part_2_star: false,
+                    lines: None,
+                    solved: None,
+                },
+            ),
+        ]);
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            None,
+            &dashboard,
+            &details,
+        )
+        .unwrap();
+        let expected = [
+            "foo",
+            "<!--- benchmarking table --->",
+            "## Dashboard",
+            "",
+            "| Day | Stars | Part 2 | Lines | Share |",
+            "| :---: | :---: | :---: | :---: | :---: |",
+            "| **[Day 4](./src/bin/04.rs)** |  | `50.0ms` | `-` | 47.4% |",
+            "| [Day 2](./src/bin/02.rs) |  | `40.0ms` | `-` | 36.8% |",
+            "| [Day 1](./src/bin/01.rs) | ⭐⭐ | `20.0ms` | 42 | 15.8% |",
+            "| [Day 5](./src/bin/05.rs) | ⭐ | `-` | `-` | `-` |",
+            "<!--- benchmarking table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn removes_stars_table_with_stars_column() {
+        let stars_marker = "<!--- advent_readme_stars table --->";
+        let mut s = format!(
+            "foo\n\n{stars_marker}\n## 2023 Results\n{stars_marker}\n\n{}\n{}\nbaz",
+            MARKER, MARKER
+        );
+        let dashboard = Dashboard {
+            columns: vec![Column::Stars, Column::Part1],
+            ..Dashboard::default()
+        };
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            None,
+            &dashboard,
+            &HashMap::new(),
+        )
+        .unwrap();
+        assert!(!s.contains(stars_marker));
+        assert!(s.starts_with(&format!("foo\n\n{MARKER}\n## Benchmarks")));
+    }
+
+    #[test]
+    fn format_solve_dates() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        let dashboard = Dashboard {
+            columns: vec![Column::Solved],
+            sort: Sort::Fastest,
+            ..Dashboard::default()
+        };
+        let details = HashMap::from([(
+            day!(2),
+            DayDetails {
+                solved: Some(1_701_475_200),
+                ..DayDetails::default()
+            },
+        )]);
+        update_content(
+            &mut s,
+            &get_mock_timings(),
+            190.0,
+            None,
+            &dashboard,
+            &details,
+        )
+        .unwrap();
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `-` |\n| [Day 2](./src/bin/02.rs) | 2023-12-02 |"
+        ));
+        assert!(s.contains("**Total: 190.00ms**"));
+    }
 }
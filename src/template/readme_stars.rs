@@ -0,0 +1,232 @@
+/// Module that updates the readme with the stars of a season, from the locally recorded answers and submissions.
+/// It replaces the table that the `advent-readme-stars` action maintains, so it works offline.
+use std::fs;
+
+use crate::template::answers::Answers;
+use crate::template::aoc_client::Outcome;
+use crate::template::dashboard::{Column, Dashboard};
+use crate::template::readme_benchmarks::{self, replace_table, Error};
+use crate::template::submissions::Submissions;
+use crate::template::timings::Timings;
+use crate::template::{all_days, default_year, Day};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+/// Marker that encloses the table of a season. Other seasons than the default one have their own table.
+pub fn marker(year: Option<u16>) -> String {
+    match year {
+        Some(year) => format!("<!--- advent_readme_stars table {year} --->"),
+        None => MARKER.into(),
+    }
+}
+
+/// The stars of a single day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DayStars {
+    pub day: Day,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+/// Collects the solved parts of a season, in day order. A part is solved if it has a known-good answer
+/// or the website accepted an answer for it. Answers to parts that were already solved are not checked,
+/// so they do not count. Days without stars are left out.
+pub fn collect_stars(answers: &Answers, submissions: &Submissions) -> Vec<DayStars> {
+    let is_solved = |day: Day, part: u8| {
+        answers.get(day, part).is_some()
+            || submissions
+                .data
+                .iter()
+                .any(|s| s.day == day && s.part == part && s.outcome == Outcome::Correct)
+    };
+
+    all_days()
+        .map(|day| DayStars {
+            day,
+            part_1: is_solved(day, 1),
+            part_2: is_solved(day, 2),
+        })
+        .filter(|stars| stars.part_1 || stars.part_2)
+        .collect()
+}
+
+fn format_star(solved: bool) -> &'static str {
+    if solved {
+        "⭐"
+    } else {
+        " "
+    }
+}
+
+fn construct_table(stars: &[DayStars], year: Option<u16>, puzzle_year: u16) -> String {
+    let marker = marker(year);
+
+    let mut lines: Vec<String> = vec![
+        marker.clone(),
+        format!("## {puzzle_year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for stars in stars {
+        let day = stars.day.into_inner();
+        lines.push(format!(
+            "| [Day {day}](https://adventofcode.com/{puzzle_year}/day/{day}) | {} | {} |",
+            format_star(stars.part_1),
+            format_star(stars.part_2)
+        ));
+    }
+
+    lines.push(marker);
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    stars: &[DayStars],
+    year: Option<u16>,
+    puzzle_year: u16,
+) -> Result<(), Error> {
+    let table = construct_table(stars, year, puzzle_year);
+    replace_table(s, &table, &marker(year), year)
+}
+
+/// Regenerates the stars table of a season. Nothing is written until at least one part is solved,
+/// so a table maintained by the Github action is kept if no answers were recorded locally.
+/// If the benchmarks table shows the stars, it is regenerated instead of a separate table.
+/// Returns whether stars were written.
+pub fn update(year: Option<u16>) -> Result<bool, Error> {
+    let stars = collect_stars(
+        &Answers::read_from_file(year).map_err(Error::Data)?,
+        &Submissions::read_from_file(year).map_err(Error::Data)?,
+    );
+
+    if Dashboard::read_from_file().shows(Column::Stars) {
+        readme_benchmarks::update(Timings::read_from_file(year), year)?;
+        return Ok(!stars.is_empty());
+    }
+
+    if stars.is_empty() {
+        return Ok(false);
+    }
+
+    // the links of the table point to the puzzles, which need the year of the default season.
+    let puzzle_year = year.or_else(default_year).ok_or_else(|| {
+        Error::Parser("Could not determine the year of the stars table, set `AOC_YEAR`.".into())
+    })?;
+
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_content(&mut readme, &stars, year, puzzle_year)?;
+    fs::write(path, &readme)?;
+    Ok(true)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect_stars, update_content, DayStars, MARKER};
+    use crate::day;
+    use crate::template::answers::Answers;
+    use crate::template::aoc_client::Outcome;
+    use crate::template::submissions::{Submission, Submissions};
+    use crate::template::Day;
+
+    fn submission(day: Day, part: u8, outcome: Outcome) -> Submission {
+        Submission {
+            day,
+            part,
+            answer: "42".into(),
+            outcome,
+            timestamp: 0,
+            wait: None,
+        }
+    }
+
+    #[test]
+    fn collects_stars_from_answers_and_submissions() {
+        let mut answers = Answers::default();
+        answers.set(day!(3), 1, "a");
+        let submissions = Submissions {
+            data: vec![
+                submission(day!(1), 1, Outcome::Correct),
+                submission(day!(1), 2, Outcome::AlreadySolved),
+                submission(day!(2), 1, Outcome::TooHigh),
+            ],
+        };
+
+        assert_eq!(
+            collect_stars(&answers, &submissions),
+            vec![
+                DayStars {
+                    day: day!(1),
+                    part_1: true,
+                    part_2: false,
+                },
+                DayStars {
+                    day: day!(3),
+                    part_1: true,
+                    part_2: false,
+                },
+            ]
+        );
+    }
+
+    fn get_mock_stars() -> Vec<DayStars> {
+        vec![
+            DayStars {
+                day: day!(1),
+                part_1: true,
+                part_2: true,
+            },
+            DayStars {
+                day: day!(12),
+                part_1: true,
+                part_2: false,
+            },
+        ]
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &get_mock_stars(), None, 2023).unwrap();
+    }
+
+    #[test]
+    fn format_stars() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_stars(), None, 2023).unwrap();
+        update_content(&mut s, &get_mock_stars(), None, 2023).unwrap();
+        let expected = [
+            "foo",
+            "<!--- advent_readme_stars table --->",
+            "## 2023 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](https://adventofcode.com/2023/day/1) | ⭐ | ⭐ |",
+            "| [Day 12](https://adventofcode.com/2023/day/12) | ⭐ |   |",
+            "<!--- advent_readme_stars table --->",
+            "baz",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn adds_table_for_other_seasons() {
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, &get_mock_stars(), Some(2022), 2022).unwrap();
+        update_content(&mut s, &get_mock_stars(), Some(2022), 2022).unwrap();
+        assert_eq!(
+            s.matches("<!--- advent_readme_stars table 2022 --->")
+                .count(),
+            2
+        );
+        assert_eq!(s.matches("## 2022 Results").count(), 1);
+        assert!(s.contains("| [Day 1](https://adventofcode.com/2022/day/1) | ⭐ | ⭐ |"));
+        assert!(s.starts_with(&format!("foo\n{}{}", MARKER, MARKER)));
+    }
+}
@@ -0,0 +1,396 @@
+/// Renders stored timings as a report in other formats than the readme table.
+use std::{collections::HashMap, fmt::Write, str::FromStr, time::Duration};
+
+use tinyjson::JsonValue;
+
+use crate::template::timings::{Status, Timings};
+use crate::template::{Day, ANSI_BOLD, ANSI_RESET};
+
+/// Width of the bars of the terminal chart, in characters.
+const BAR_WIDTH: usize = 40;
+
+/// Output format of `cargo time --report`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReportFormat {
+    Csv,
+    Json,
+    /// A standalone page with a log-scale bar chart.
+    Html,
+    /// A log-scale bar chart for the terminal.
+    Terminal,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ReportFormat::Csv),
+            "json" => Ok(ReportFormat::Json),
+            "html" => Ok(ReportFormat::Html),
+            "terminal" => Ok(ReportFormat::Terminal),
+            _ => Err(format!(
+                "unknown report format \"{s}\", expected csv, json, html or terminal"
+            )),
+        }
+    }
+}
+
+/// The timings of a season, one row per part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Report {
+    pub title: String,
+    pub rows: Vec<Row>,
+    pub total: Duration,
+}
+
+/// The benchmark of a single part. Parts without a median did not complete, see `status`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Row {
+    pub day: Day,
+    pub part: u8,
+    pub median: Option<Duration>,
+    pub spread: Option<Duration>,
+    pub status: Status,
+}
+
+impl Report {
+    pub fn new(timings: &Timings, year: Option<u16>) -> Self {
+        let rows = timings
+            .data
+            .iter()
+            .flat_map(|timing| {
+                [
+                    (1, timing.part_1, timing.part_1_spread),
+                    (2, timing.part_2, timing.part_2_spread),
+                ]
+                .map(|(part, median, spread)| Row {
+                    day: timing.day,
+                    part,
+                    median,
+                    spread,
+                    status: timing.status,
+                })
+            })
+            .collect();
+
+        Report {
+            title: match year {
+                Some(year) => format!("{year} Benchmarks"),
+                None => "Benchmarks".into(),
+            },
+            rows,
+            total: timings.total(),
+        }
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Csv => self.to_csv(),
+            ReportFormat::Json => JsonValue::from(self)
+                .format()
+                .map_or_else(|e| e.to_string(), |json| json + "\n"),
+            ReportFormat::Html => self.to_html(),
+            ReportFormat::Terminal => self.to_terminal(),
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let mut csv = String::from("day,part,median_nanos,spread_nanos,status\n");
+
+        for row in &self.rows {
+            let _ = writeln!(
+                csv,
+                "{},{},{},{},{}",
+                row.day,
+                row.part,
+                row.median
+                    .map_or(String::new(), |d| d.as_nanos().to_string()),
+                row.spread
+                    .map_or(String::new(), |d| d.as_nanos().to_string()),
+                row.status
+            );
+        }
+
+        csv
+    }
+
+    fn to_html(&self) -> String {
+        let max = self.max_median();
+        let mut rows = String::new();
+
+        for row in &self.rows {
+            let (median, chart) = match row.median {
+                Some(median) => (
+                    format_median(row),
+                    format!(
+                        "<div class=\"bar\" style=\"width: {:.1}%\"></div>",
+                        log_scale(median, max) * 100_f64
+                    ),
+                ),
+                None if row.status == Status::Ok => (format_status(row), String::new()),
+                None => (
+                    format!("<span class=\"status\">{}</span>", format_status(row)),
+                    String::new(),
+                ),
+            };
+
+            let _ = writeln!(
+                rows,
+                "      <tr><td>{}</td><td>{}</td><td>{median}</td><td class=\"chart\">{chart}</td></tr>",
+                row.day.into_inner(),
+                row.part,
+            );
+        }
+
+        format!(
+            r#"<!DOCTYPE html>
+<html lang="en">
+  <head>
+    <meta charset="utf-8">
+    <title>{title}</title>
+    <style>
+      body {{ font-family: sans-serif; margin: 2rem; }}
+      td {{ padding: 0.1rem 0.5rem; white-space: nowrap; }}
+      .chart {{ width: 30rem; }}
+      .bar {{ height: 0.8rem; background: #2e7d32; }}
+      .status {{ color: #b71c1c; }}
+    </style>
+  </head>
+  <body>
+    <h1>{title}</h1>
+    <p>Total: {total:.2}ms. Bars use a logarithmic scale.</p>
+    <table>
+      <tr><th>Day</th><th>Part</th><th>Median</th><th></th></tr>
+{rows}    </table>
+  </body>
+</html>
+"#,
+            title = self.title,
+            total = self.total.as_secs_f64() * 1000_f64,
+        )
+    }
+
+    fn to_terminal(&self) -> String {
+        let max = self.max_median();
+        let mut chart = format!("{ANSI_BOLD}{}{ANSI_RESET}\n\n", self.title);
+
+        for row in &self.rows {
+            let bar = match row.median {
+                Some(median) => format!(
+                    "{:<width$} {}",
+                    bar(log_scale(median, max)),
+                    format_median(row),
+                    width = BAR_WIDTH
+                ),
+                None => format_status(row),
+            };
+
+            let _ = writeln!(chart, "Day {} Part {} {bar}", row.day, row.part);
+        }
+
+        let _ = writeln!(
+            chart,
+            "\n{ANSI_BOLD}Total:{ANSI_RESET} {:.2}ms (log scale)",
+            self.total.as_secs_f64() * 1000_f64
+        );
+        chart
+    }
+
+    fn max_median(&self) -> Duration {
+        self.rows
+            .iter()
+            .filter_map(|row| row.median)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+fn format_median(row: &Row) -> String {
+    match (row.median, row.spread) {
+        (Some(median), Some(spread)) => format!("{median:.1?} ± {spread:.1?}"),
+        (Some(median), None) => format!("{median:.1?}"),
+        (None, _) => format_status(row),
+    }
+}
+
+/// Labels a part without a median. If its day completed, the part was not solved.
+fn format_status(row: &Row) -> String {
+    match row.status {
+        Status::Ok => "-".into(),
+        status => status.to_string(),
+    }
+}
+
+/// Position of a duration on a logarithmic scale from 1ns to `max`, between 0 and 1.
+#[allow(clippy::cast_precision_loss)]
+fn log_scale(duration: Duration, max: Duration) -> f64 {
+    let max = (max.as_nanos() as f64).ln_1p();
+    if max == 0_f64 {
+        return 0_f64;
+    }
+    (duration.as_nanos() as f64).ln_1p() / max
+}
+
+/// Renders a bar of `BAR_WIDTH` characters filled to `fraction`, with eighth blocks for the remainder.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_precision_loss,
+    clippy::cast_sign_loss
+)]
+fn bar(fraction: f64) -> String {
+    const PARTIAL: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    let eighths = (fraction.clamp(0_f64, 1_f64) * (BAR_WIDTH * 8) as f64).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    match eighths % 8 {
+        0 => {}
+        remainder => bar.push(PARTIAL[remainder]),
+    }
+    bar
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+fn duration_or_null(duration: Option<Duration>) -> JsonValue {
+    duration.map_or(JsonValue::Null, |d| JsonValue::Number(d.as_nanos() as f64))
+}
+
+impl From<&Report> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Report) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("title".into(), JsonValue::String(value.title.clone()));
+        map.insert(
+            "total_nanos".into(),
+            JsonValue::Number(value.total.as_nanos() as f64),
+        );
+        map.insert(
+            "rows".into(),
+            JsonValue::Array(value.rows.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&Row> for JsonValue {
+    fn from(value: &Row) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("median_nanos".into(), duration_or_null(value.median));
+        map.insert("spread_nanos".into(), duration_or_null(value.spread));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{bar, log_scale, Report, ReportFormat, BAR_WIDTH};
+    use crate::day;
+    use crate::template::timings::{Status, Timing, Timings};
+
+    fn get_mock_report() -> Report {
+        let timings = Timings {
+            data: vec![
+                Timing {
+                    part_1_spread: Some(Duration::from_micros(1)),
+                    ..Timing::ok(
+                        day!(1),
+                        Some(Duration::from_micros(10)),
+                        Some(Duration::from_millis(20)),
+                    )
+                },
+                Timing::new(day!(2), Status::Timeout),
+                Timing::ok(day!(3), Some(Duration::from_millis(1)), None),
+            ],
+            history: vec![],
+        };
+
+        Report::new(&timings, Some(2022))
+    }
+
+    #[test]
+    fn builds_one_row_per_part() {
+        let report = get_mock_report();
+        assert_eq!(report.title, "2022 Benchmarks");
+        assert_eq!(report.rows.len(), 6);
+        assert_eq!(report.rows[1].median, Some(Duration::from_millis(20)));
+        assert_eq!(report.rows[3].status, Status::Timeout);
+        assert_eq!(report.total, Duration::from_micros(21_010));
+    }
+
+    #[test]
+    fn renders_csv() {
+        let csv = get_mock_report().render(ReportFormat::Csv);
+        assert_eq!(
+            csv,
+            [
+                "day,part,median_nanos,spread_nanos,status",
+                "01,1,10000,1000,ok",
+                "01,2,20000000,,ok",
+                "02,1,,,timeout",
+                "02,2,,,timeout",
+                "03,1,1000000,,ok",
+                "03,2,,,ok",
+                "",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn renders_json() {
+        let json = get_mock_report().render(ReportFormat::Json);
+        let value: tinyjson::JsonValue = json.parse().unwrap();
+        assert_eq!(value["rows"][0]["median_nanos"], 10_000_f64.into());
+        assert!(value["rows"][2]["median_nanos"].is_null());
+    }
+
+    #[test]
+    fn renders_html() {
+        let html = get_mock_report().render(ReportFormat::Html);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<td>1</td><td>2</td><td>20.0ms</td>"));
+        assert!(html.contains("style=\"width: 100.0%\""));
+        assert!(html
+            .contains("<td><span class=\"status\">timeout</span></td><td class=\"chart\"></td>"));
+        assert!(html.contains("<td>3</td><td>2</td><td>-</td><td class=\"chart\"></td>"));
+    }
+
+    #[test]
+    fn renders_terminal_chart() {
+        let chart = get_mock_report().render(ReportFormat::Terminal);
+        assert!(chart.contains(&format!("Day 01 Part 2 {} 20.0ms", "█".repeat(BAR_WIDTH))));
+        assert!(chart.contains("Day 02 Part 1 timeout"));
+        assert!(chart.contains("Day 03 Part 2 -\n"));
+    }
+
+    #[test]
+    fn scales_logarithmically() {
+        let max = Duration::from_nanos(999);
+        assert!((log_scale(max, max) - 1_f64).abs() < 1e-9);
+        assert!((log_scale(Duration::from_nanos(31), max) - 0.5).abs() < 0.01);
+        assert_eq!(log_scale(max, Duration::ZERO), 0_f64);
+    }
+
+    #[test]
+    fn renders_partial_bars() {
+        assert_eq!(bar(0_f64), "");
+        assert_eq!(bar(1_f64).chars().count(), BAR_WIDTH);
+        assert_eq!(bar(0.5 / BAR_WIDTH as f64), "▌");
+    }
+}
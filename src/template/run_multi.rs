@@ -1,102 +1,475 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    io,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+    time::Duration,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    history::RunDay,
+    runner::{InputSource, PartResult, Solution},
+    Day, Puzzle, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    timings::{Status, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Represents the results of running the solution of a single day.
+#[derive(Clone, Debug)]
+pub struct DayRun {
+    pub day: Day,
+    pub results: Vec<PartResult>,
+    pub status: Status,
+}
 
-    let mut need_space = false;
+impl DayRun {
+    pub fn timing(&self) -> Timing {
+        child_commands::collect_timing(&self.results, self.day, self.status)
+    }
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    /// Returns the benched parts of the day, as recorded in the history of the timings file.
+    pub fn history(&self) -> RunDay {
+        RunDay::new(self.day, self.status, &self.results)
+    }
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+/// Collects the timings of a set of runs.
+pub fn collect_timings(runs: &[DayRun]) -> Timings {
+    Timings {
+        data: runs.iter().map(DayRun::timing).collect(),
+        history: vec![],
+    }
+}
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+/// Adds the heap usage reported by `runs` to the timings of the same day.
+pub fn collect_memory(timings: &mut Timings, runs: &[DayRun]) {
+    for run in runs {
+        let Some(timing) = timings.data.iter_mut().find(|t| t.day == run.day) else {
+            continue;
+        };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+        for result in &run.results {
+            match result.part {
+                1 => timing.part_1_memory = result.memory,
+                2 => timing.part_2_memory = result.memory,
+                _ => {}
             }
-        });
+        }
+    }
+}
+
+/// Controls how [`run_multi`] runs solutions.
+pub struct RunOptions<'a> {
+    /// Season of the days to run, `None` for the default season.
+    pub year: Option<u16>,
+    pub is_release: bool,
+    pub is_timed: bool,
+    /// Build solutions with the `dhat` profile to record their heap usage. Always runs solutions via `cargo run --bin`.
+    pub is_dhat: bool,
+    /// Solutions compiled into the main binary. Days without an entry fall back to `cargo run --bin`.
+    pub solutions: &'a [Solution],
+    /// Number of days to run concurrently. Timed runs are always serial to keep measurements clean.
+    pub jobs: usize,
+    /// Maximum duration a single day may run for before it is stopped.
+    pub timeout: Option<Duration>,
+}
+
+/// Runs the solutions of the given days. Days that are not solved yet are not part of the returned runs.
+pub fn run_multi(days_to_run: &HashSet<Day>, options: &RunOptions) -> Vec<DayRun> {
+    // NOTE: use non-duplicate, sorted day values.
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let runs = if options.jobs > 1 && !options.is_timed {
+        run_parallel(&days, options.jobs, |day, output| {
+            run_day(day, options, output)
+        })
+    } else {
+        run_serial(&days, options)
+    };
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
+    if options.is_timed {
+        let total_millis = collect_timings(&runs).total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+    }
+
+    print_status_summary(&runs);
+
+    runs
+}
+
+/// Lists days that did not run successfully, grouped by status.
+fn print_status_summary(runs: &[DayRun]) {
+    for status in [
+        Status::Unsolved,
+        Status::Panic,
+        Status::Timeout,
+        Status::Error,
+    ] {
+        let days: Vec<String> = runs
+            .iter()
+            .filter(|run| run.status == status)
+            .map(|run| run.day.to_string())
+            .collect();
+
+        if !days.is_empty() {
+            println!(
+                "{ANSI_BOLD}{}:{ANSI_RESET} {}",
+                status_label(status),
+                days.join(", ")
+            );
+        }
+    }
+}
+
+fn status_label(status: Status) -> &'static str {
+    match status {
+        Status::Ok => "Ok",
+        Status::Unsolved => "Unsolved",
+        Status::Panic => "Panicked",
+        Status::Timeout => "Timed out",
+        Status::Error => "Failed",
+    }
+}
+
+fn run_serial(days: &[Day], options: &RunOptions) -> Vec<DayRun> {
+    days.iter()
+        .enumerate()
+        .filter_map(|(i, day)| {
+            if i > 0 {
+                println!();
+            }
+            run_day(*day, options, &mut DayOutput::direct())
+        })
+        .collect()
+}
+
+/// Runs days on `jobs` worker threads. The output of each day is buffered
+/// and printed as one block, in day order, as soon as all previous days have completed.
+fn run_parallel(
+    days: &[Day],
+    jobs: usize,
+    run_day: impl Fn(Day, &mut DayOutput) -> Option<DayRun> + Sync,
+) -> Vec<DayRun> {
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let next_index = &next_index;
+            let run_day = &run_day;
+
+            scope.spawn(move || loop {
+                let i = next_index.fetch_add(1, Ordering::SeqCst);
+                let Some(day) = days.get(i) else {
+                    break;
+                };
+
+                let mut output = DayOutput::buffered();
+                let run = run_day(*day, &mut output);
+
+                if sender.send((i, output, run)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(sender);
+
+        let mut runs = vec![];
+        let mut pending = HashMap::new();
+        let mut next_to_print = 0;
+
+        for (i, output, run) in receiver {
+            pending.insert(i, (output, run));
+
+            while let Some((mut output, run)) = pending.remove(&next_to_print) {
+                if next_to_print > 0 {
+                    println!();
+                }
+                output.flush();
+                runs.extend(run);
+                next_to_print += 1;
+            }
+        }
+
+        runs
+    })
+}
+
+fn run_day(day: Day, options: &RunOptions, output: &mut DayOutput) -> Option<DayRun> {
+    let puzzle = Puzzle {
+        year: options.year,
+        day,
+    };
+
+    output.println(format!("{ANSI_BOLD}Day {puzzle}{ANSI_RESET}"));
+    output.println("------");
+
+    let results = match find_solution(puzzle, options) {
+        Some(solution) => in_process::run_solution(solution, options, output),
+        None => child_commands::run_solution(puzzle, &InputSource::Puzzle, options, output),
+    };
+
+    let (results, status) = match results {
+        Ok(results) => results,
+        Err(e) => {
+            output.eprintln(format!("Failed to run solution: {e}"));
+            (vec![], Status::Error)
+        }
+    };
+
+    match status {
+        Status::Ok if results.is_empty() => {
+            output.println("Not solved.");
+            return None;
+        }
+        Status::Timeout => output.println(format!(
+            "Timed out after {:?}.",
+            options.timeout.unwrap_or_default()
+        )),
+        Status::Panic => output.println("Panicked."),
+        Status::Error => output.println("Failed."),
+        _ => {}
+    }
+
+    let status = if status == Status::Ok && results.iter().all(|r| r.answer.is_none()) {
+        Status::Unsolved
     } else {
-        None
+        status
+    };
+
+    Some(DayRun {
+        day,
+        results,
+        status,
+    })
+}
+
+/// Returns the solution of a puzzle compiled into the main binary, if it can run in-process with `options`.
+/// The main binary is not built with the dhat allocator and has a single profile,
+/// so profiled days and days that request another profile run as separate binaries.
+/// A thread can not be stopped, so days with a timeout also run as separate binaries, which are killed.
+pub fn find_solution<'a>(puzzle: Puzzle, options: &RunOptions<'a>) -> Option<&'a Solution> {
+    if options.is_dhat || options.timeout.is_some() || options.is_release == cfg!(debug_assertions)
+    {
+        return None;
+    }
+
+    options.solutions.iter().find(|s| s.puzzle == puzzle)
+}
+
+/// The output of a single day. Direct output is printed immediately,
+/// buffered output is kept until [`DayOutput::flush`] so that concurrent days do not interleave.
+pub struct DayOutput {
+    is_buffered: bool,
+    lines: Vec<(bool, String)>,
+}
+
+impl DayOutput {
+    fn direct() -> Self {
+        Self {
+            is_buffered: false,
+            lines: vec![],
+        }
+    }
+
+    pub fn buffered() -> Self {
+        Self {
+            is_buffered: true,
+            lines: vec![],
+        }
+    }
+
+    pub fn is_buffered(&self) -> bool {
+        self.is_buffered
+    }
+
+    pub fn println(&mut self, line: impl Into<String>) {
+        self.push(false, line.into());
+    }
+
+    pub fn eprintln(&mut self, line: impl Into<String>) {
+        self.push(true, line.into());
+    }
+
+    fn push(&mut self, is_stderr: bool, line: String) {
+        self.lines.push((is_stderr, line));
+        if !self.is_buffered {
+            self.flush();
+        }
+    }
+
+    pub fn flush(&mut self) {
+        for (is_stderr, line) in self.lines.drain(..) {
+            if is_stderr {
+                eprintln!("{line}");
+            } else {
+                println!("{line}");
+            }
+        }
     }
 }
 
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    MissingInput(String),
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not capture output of child process."),
+            Error::MissingInput(path) => write!(f, "input file \"{path}\" does not exist."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Solutions that are compiled into the main binary can be called directly, skipping `cargo run`.
+pub mod in_process {
+    use super::{DayOutput, Error, RunOptions};
+    use crate::template::{
+        runner::{PartResult, Solution},
+        timings::Status,
+    };
+    use std::{fs, thread};
+
+    /// Same as the default stack size of the main thread on most platforms.
+    const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+    /// Run a solution with the input for its day and print the results it reports for each part.
+    /// Note that output the solution prints itself is not captured by `output`.
+    pub fn run_solution(
+        solution: &Solution,
+        options: &RunOptions,
+        output: &mut DayOutput,
+    ) -> Result<(Vec<PartResult>, Status), Error> {
+        let input_path = solution.puzzle.input_path();
+        let input = fs::read_to_string(&input_path).map_err(|_| Error::MissingInput(input_path))?;
+
+        let (results, status) = run_with_input(solution, input, options.is_timed)?;
+        results.iter().for_each(|r| output.println(r.to_string()));
+        Ok((results, status))
+    }
+
+    /// Run a solution with the given input.
+    ///
+    /// The solution runs on its own thread, so a panic does not affect other days.
+    pub fn run_with_input(
+        solution: &Solution,
+        input: String,
+        is_timed: bool,
+    ) -> Result<(Vec<PartResult>, Status), Error> {
+        let run = solution.run;
+
+        let thread = thread::Builder::new()
+            .stack_size(STACK_SIZE)
+            .spawn(move || run(&input, is_timed))?;
+
+        match thread.join() {
+            Ok(results) => Ok((results, Status::Ok)),
+            Err(_) => Ok((vec![], Status::Panic)),
+        }
+    }
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as collecting their results.
 pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use super::{DayOutput, Error, RunOptions};
+    use crate::template::{
+        runner::{InputSource, PartResult},
+        timings::Status,
+        Day, Puzzle,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
+        sync::mpsc::{self, RecvTimeoutError},
         thread,
+        time::Instant,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Exit code of a Rust program that panicked.
+    const PANIC_EXIT_CODE: i32 = 101;
+
+    /// Run the solution bin for a given puzzle and collect the results it reports for each part.
+    /// The bin is built before it runs, so the timeout does not include compiling the solution.
+    /// The child is killed if it exceeds the timeout.
+    pub fn run_solution(
+        puzzle: Puzzle,
+        input: &InputSource,
+        options: &RunOptions,
+        output: &mut DayOutput,
+    ) -> Result<(Vec<PartResult>, Status), Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&puzzle.bin_path()).exists() {
+            return Ok((vec![], Status::Ok));
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        if let Some(input_path) = input.path(puzzle) {
+            if !Path::new(&input_path).exists() {
+                return Err(Error::MissingInput(input_path));
+            }
+        }
+
+        let bin_name = puzzle.bin_name();
+        let profile_args: &[&str] = if options.is_dhat {
+            &["--profile", "dhat", "--features", "dhat-heap"]
+        } else if options.is_release {
+            &["--release"]
+        } else {
+            &[]
+        };
+
+        // a solution that does not compile is reported as an error, not as a panic.
+        let build = Command::new("cargo")
+            .args(["build", "--quiet", "--bin", &bin_name])
+            .args(profile_args)
+            .output()?;
 
-        if is_release {
-            args.push("--release");
+        if !build.status.success() {
+            for line in String::from_utf8_lossy(&build.stderr).lines() {
+                output.eprintln(line);
+            }
+            return Ok((vec![], Status::Error));
         }
 
-        if is_timed {
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
+        args.extend(profile_args);
+
+        // request machine-readable output from the child.
+        args.extend(["--", "--format", "json"]);
+
+        let input_args = input.to_args();
+        args.extend(input_args.iter().map(String::as_str));
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr and any non-protocol stdout lines while collecting results.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -107,150 +480,315 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut results = vec![];
 
+        // when buffering, stderr is collected and added to the output once the child exits.
+        let is_buffered = output.is_buffered();
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+            let mut lines = vec![];
+            for line in stderr.lines().map_while(Result::ok) {
+                if is_buffered {
+                    lines.push(line);
+                } else {
+                    eprintln!("{line}");
+                }
+            }
+            lines
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        // read stdout on a separate thread, so the child can be killed while waiting for output.
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let deadline = options.timeout.map(|timeout| Instant::now() + timeout);
+        let mut is_timeout = false;
 
-        Ok(output)
-    }
+        loop {
+            let line = match deadline {
+                Some(deadline) => {
+                    receiver.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+
+            match line {
+                Ok(line) => match parse_result(&line, puzzle.day) {
+                    Some(result) => {
+                        output.println(result.to_string());
+                        results.push(result);
+                    }
+                    None => output.println(line),
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    is_timeout = true;
+                    cmd.kill()?;
+                    break;
+                }
+                Err(RecvTimeoutError::Disconnected) => break,
+            }
+        }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+        for line in thread.join().unwrap() {
+            output.eprintln(line);
+        }
+        let exit_status = cmd.wait()?;
+
+        let status = if is_timeout {
+            Status::Timeout
+        } else if exit_status.success() {
+            Status::Ok
+        } else if exit_status.code() == Some(PANIC_EXIT_CODE) {
+            Status::Panic
+        } else {
+            Status::Error
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        Ok((results, status))
+    }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+    /// Parses a line of the child's output as a [`PartResult`] for the given day.
+    /// Returns `None` for other output, e.g. debug prints of the solution.
+    pub fn parse_result(line: &str, day: Day) -> Option<PartResult> {
+        line.parse::<PartResult>()
+            .ok()
+            .filter(|result| result.day == day)
+    }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+    /// Builds the timing for a day from the results of its parts.
+    pub fn collect_timing(results: &[PartResult], day: Day, status: Status) -> super::Timing {
+        let mut timings = super::Timing::new(day, status);
+
+        results
+            .iter()
+            .filter(|result| result.answer.is_some() && result.stats.samples > 1)
+            .for_each(|result| {
+                let stats = &result.stats;
+
+                match result.part {
+                    1 => {
+                        timings.part_1 = Some(stats.median);
+                        timings.part_1_spread = Some(stats.stddev);
+                    }
+                    2 => {
+                        timings.part_2 = Some(stats.median);
+                        timings.part_2_spread = Some(stats.stddev);
+                    }
+                    _ => {}
                 }
 
-                timings.total_nanos += nanos;
+                timings.total += stats.median;
             });
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
-
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
+        use super::{collect_timing, parse_result};
+        use crate::{
+            day,
+            template::{
+                runner::{BenchStats, PartResult},
+                timings::Status,
+            },
+        };
+        use std::time::Duration;
+
+        fn get_mock_result(
+            part: u8,
+            answer: Option<&str>,
+            nanos: u64,
+            samples: u128,
+        ) -> PartResult {
+            let duration = Duration::from_nanos(nanos);
+            PartResult {
+                day: day!(1),
+                part,
+                answer: answer.map(Into::into),
+                stats: BenchStats {
+                    min: duration,
+                    median: duration,
+                    p95: duration,
+                    stddev: Duration::from_nanos(nanos / 10),
+                    samples,
+                },
+                memory: None,
+            }
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
+        fn collects_execution_times() {
+            let res = collect_timing(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    get_mock_result(1, Some("0"), 74, 100_000),
+                    get_mock_result(2, Some("10"), 74_130_000, 99_999),
                 ],
                 day!(1),
+                Status::Ok,
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.total, Duration::from_nanos(74_130_074));
+            assert_eq!(res.part_1, Some(Duration::from_nanos(74)));
+            assert_eq!(res.part_1_spread, Some(Duration::from_nanos(7)));
+            assert_eq!(res.part_2, Some(Duration::from_micros(74_130)));
+            assert_eq!(res.part_2_spread, Some(Duration::from_micros(7_413)));
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+        fn collects_missing_parts() {
+            let res = collect_timing(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    get_mock_result(1, None, 10, 100),
+                    get_mock_result(2, None, 10, 100),
                 ],
                 day!(1),
+                Status::Unsolved,
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.total, Duration::ZERO);
+            assert_eq!(res.status, Status::Unsolved);
+            assert_eq!(res.part_1.is_none(), true);
+            assert_eq!(res.part_2.is_none(), true);
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
+        fn ignores_unbenched_parts() {
+            let res = collect_timing(&[get_mock_result(1, Some("1"), 10, 1)], day!(1), Status::Ok);
             assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn parses_results_with_patterns_in_answer() {
+            let result = get_mock_result(1, Some("@ @ ( ) ms (2s @ 5 samples)"), 2, 5);
+            let parsed = parse_result(&result.to_json_line(), day!(1)).unwrap();
+            assert_eq!(parsed, result);
+        }
+
+        #[test]
+        fn passes_through_other_output() {
+            assert_eq!(
+                parse_result("Part 1: 0 (74.13ns @ 100000 samples)", day!(1)),
+                None
+            );
+            assert_eq!(parse_result("{}", day!(1)), None);
+            let other_day = get_mock_result(1, Some("1"), 10, 1).to_json_line();
+            assert_eq!(parse_result(&other_day, day!(2)), None);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::{find_solution, in_process::run_with_input, run_parallel, DayRun, RunOptions};
+    use crate::day;
+    use crate::template::runner::{BenchStats, PartResult, Solution};
+    use crate::template::timings::Status;
+    use crate::template::{Day, Puzzle};
+
+    fn count_lines(input: &str, _is_timed: bool) -> Vec<PartResult> {
+        vec![PartResult {
+            day: day!(1),
+            part: 1,
+            answer: Some(input.lines().count().to_string()),
+            stats: BenchStats::single(Duration::ZERO),
+            memory: None,
+        }]
+    }
+
+    fn panics(_input: &str, _is_timed: bool) -> Vec<PartResult> {
+        panic!("expected panic of a mock solution");
+    }
+
+    const SOLUTIONS: &[Solution] = &[
+        Solution {
+            puzzle: Puzzle {
+                year: None,
+                day: day!(1),
+            },
+            run: count_lines,
+        },
+        Solution {
+            puzzle: Puzzle {
+                year: None,
+                day: day!(2),
+            },
+            run: panics,
+        },
+    ];
+
+    fn get_mock_options() -> RunOptions<'static> {
+        RunOptions {
+            year: None,
+            is_release: !cfg!(debug_assertions),
+            is_timed: false,
+            is_dhat: false,
+            solutions: SOLUTIONS,
+            jobs: 1,
+            timeout: None,
+        }
+    }
+
+    #[test]
+    fn finds_solutions_of_the_same_profile() {
+        let puzzle = Puzzle::from(day!(1));
+        assert!(find_solution(puzzle, &get_mock_options()).is_some());
+        assert!(find_solution(Puzzle::from(day!(3)), &get_mock_options()).is_none());
+
+        let other_profile = RunOptions {
+            is_release: cfg!(debug_assertions),
+            ..get_mock_options()
+        };
+        assert!(find_solution(puzzle, &other_profile).is_none());
+
+        let dhat = RunOptions {
+            is_dhat: true,
+            ..get_mock_options()
+        };
+        assert!(find_solution(puzzle, &dhat).is_none());
+
+        let timeout = RunOptions {
+            timeout: Some(Duration::from_secs(1)),
+            ..get_mock_options()
+        };
+        assert!(find_solution(puzzle, &timeout).is_none());
+    }
+
+    #[test]
+    fn runs_solutions_in_process() {
+        let (results, status) = run_with_input(&SOLUTIONS[0], "a\nb".into(), false).unwrap();
+        assert_eq!(status, Status::Ok);
+        assert_eq!(results[0].answer.as_deref(), Some("2"));
+    }
+
+    #[test]
+    fn isolates_panics() {
+        let (results, status) = run_with_input(&SOLUTIONS[1], String::new(), false).unwrap();
+        assert_eq!(status, Status::Panic);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn keeps_day_order_of_parallel_runs() {
+        let days = [day!(1), day!(2), day!(3), day!(4)];
+
+        // earlier days take longer, so they complete last.
+        let runs = run_parallel(&days, 4, |day: Day, _output| {
+            thread::sleep(Duration::from_millis(10 * u64::from(5 - day.into_inner())));
+            // unsolved days are not part of the runs.
+            (day != day!(3)).then(|| DayRun {
+                day,
+                results: vec![],
+                status: Status::Ok,
+            })
+        });
+
+        let days: Vec<Day> = runs.iter().map(|run| run.day).collect();
+        assert_eq!(days, vec![day!(1), day!(2), day!(4)]);
     }
 }
@@ -1,70 +1,383 @@
 /// Encapsulates code that interacts with solution functions.
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::io::{stdout, Read, Write};
+use std::str::FromStr;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, fs, io};
 
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{AocClient, Outcome, SubmitResponse};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    answers, readme_stars, submissions, Day, Error, Puzzle, ANSI_ITALIC, ANSI_RESET,
+};
+
+/// Options of a solution binary, e.g. `cargo run --bin 01 -- --time --part 1`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RunOptions {
+    /// Bench every part instead of running it once, `--time`.
+    pub time: bool,
+    /// Submit the answer of this part, `--submit <part>`.
+    pub submit: Option<u8>,
+    /// Where the input is read from, `--input <path>`, `--stdin` or `--example [<index>]`.
+    pub input: InputSource,
+    /// Only run this part, `--part <part>`.
+    pub part: Option<u8>,
+    /// How results are printed, `--format <text|json>`.
+    pub format: Format,
+}
+
+/// Output format of a solution binary.
+/// With `json`, each part prints a single line of JSON instead of human-readable output.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format \"{s}\", expected text or json")),
+        }
+    }
+}
+
+/// Where a solution binary reads its input from.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum InputSource {
+    /// The input of the puzzle, e.g. `data/inputs/01.txt`.
+    #[default]
+    Puzzle,
+    /// Any file, `--input <path>`.
+    File(String),
+    /// Standard input, `--stdin`.
+    Stdin,
+    /// An example file, `--example [<index>]`, e.g. `data/examples/01_2.txt` for `--example 2`.
+    Example(Option<u8>),
+}
+
+impl InputSource {
+    /// Parses `--input <path>`, `--stdin` and `--example [<index>]`, which exclude each other.
+    ///
+    /// The index of `--example` is a free argument, so this has to be called after
+    /// every other option and free argument was parsed.
+    pub fn from_args(args: &mut pico_args::Arguments) -> Result<Self, Error> {
+        let path: Option<String> = args.opt_value_from_str("--input")?;
+        let stdin = args.contains("--stdin");
+        let example = args.contains("--example");
+
+        match (path, stdin, example) {
+            (None, false, false) => Ok(InputSource::Puzzle),
+            (Some(path), false, false) => Ok(InputSource::File(path)),
+            (None, true, false) => Ok(InputSource::Stdin),
+            (None, false, true) => Ok(InputSource::Example(args.opt_free_from_str()?)),
+            _ => Err(Error::Usage(
+                "only one of --input, --stdin and --example can be passed".into(),
+            )),
+        }
+    }
+
+    /// The arguments that select this input, to pass them on to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::Puzzle => vec![],
+            InputSource::File(path) => vec!["--input".into(), path.clone()],
+            InputSource::Stdin => vec!["--stdin".into()],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(index)) => vec!["--example".into(), index.to_string()],
+        }
+    }
+
+    /// Path of the file this source reads the input of a puzzle from, `None` for standard input.
+    pub fn path(&self, puzzle: Puzzle) -> Option<String> {
+        match self {
+            InputSource::Puzzle => Some(puzzle.input_path()),
+            InputSource::File(path) => Some(path.clone()),
+            InputSource::Example(index) => Some(puzzle.indexed_example_path(*index)),
+            InputSource::Stdin => None,
+        }
+    }
+
+    /// Reads the input of a puzzle from this source.
+    pub fn read(&self, puzzle: Puzzle) -> Result<String, Error> {
+        let Some(path) = self.path(puzzle) else {
+            let mut input = String::new();
+            io::stdin().read_to_string(&mut input)?;
+            return Ok(input);
+        };
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+        fs::read_to_string(&path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => Error::MissingFile(path),
+            _ => Error::IO(e),
+        })
+    }
+}
+
+const USAGE: &str =
+    "Usage: cargo run --bin <day> -- [--time] [--submit <part>] [--part <part>] [--format <text|json>] [--input <path> | --stdin | --example [<index>]]";
+
+impl RunOptions {
+    /// Parses the options passed to the running binary.
+    pub fn from_env() -> Result<Self, Error> {
+        Self::parse(pico_args::Arguments::from_env())
+    }
+
+    /// Parses the options of a solution binary, unknown arguments are an error.
+    pub fn parse(mut args: pico_args::Arguments) -> Result<Self, Error> {
+        let options =
+            Self::parse_known(&mut args).map_err(|e| Error::Usage(format!("{e}.\n{USAGE}")))?;
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            return Err(Error::Usage(format!(
+                "Unknown argument(s): {remaining:?}.\n{USAGE}"
+            )));
+        }
+
+        if options.submit.is_some() && options.input != InputSource::Puzzle {
+            return Err(Error::Usage(
+                "--submit can only be used with the puzzle input.".into(),
+            ));
+        }
+
+        Ok(options)
+    }
+
+    fn parse_known(args: &mut pico_args::Arguments) -> Result<Self, Error> {
+        let time = args.contains("--time");
+        let submit = args.opt_value_from_fn("--submit", parse_part)?;
+        let part = args.opt_value_from_fn("--part", parse_part)?;
+        let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+
+        Ok(RunOptions {
+            time,
+            submit,
+            input: InputSource::from_args(args)?,
+            part,
+            format,
+        })
+    }
+
+    /// Whether a part should run, all parts run unless `--part` was passed.
+    pub fn runs_part(&self, part: u8) -> bool {
+        self.part.is_none_or(|p| p == part)
+    }
+
+    /// Reads the input of the puzzle from the selected source.
+    pub fn read_input(&self, puzzle: Puzzle) -> Result<String, Error> {
+        self.input.read(puzzle)
+    }
+}
+
+fn parse_part(s: &str) -> Result<u8, String> {
+    match s.parse::<u8>() {
+        Ok(part @ (1 | 2)) => Ok(part),
+        _ => Err(format!("\"{s}\" is not a part, expected 1 or 2")),
+    }
+}
+
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    puzzle: Puzzle,
+    part: u8,
+    options: &RunOptions,
+) -> Result<(), Error> {
     let part_str = format!("Part {part}");
+    let is_json = options.format == Format::Json;
+    let is_timed = options.time;
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats, memory) = run_timed(func, input, is_timed, |result| {
+        if !is_json {
+            print_result(result, &part_str, "");
+
+            if is_timed {
+                print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                let _ = stdout().flush();
+            }
+        }
+    });
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if is_json {
+        let part_result = PartResult {
+            day: puzzle.day,
+            part,
+            answer: result.as_ref().map(ToString::to_string),
+            stats,
+            memory,
+        };
+        println!("{}", part_result.to_json_line());
+    } else {
+        let memory_str = memory.as_ref().map_or(String::new(), format_memory);
+        print_result(
+            &result,
+            &part_str,
+            &format!("{}{memory_str}", format_duration(&stats)),
+        );
+    }
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(result) = result.filter(|_| options.submit == Some(part)) {
+        submit_result(result, puzzle, part)?;
     }
+
+    Ok(())
+}
+
+/// Run a solution part without printing anything and return its result.
+/// This is used to run solutions in-process, see [`Solution`].
+pub fn solve_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    day: Day,
+    part: u8,
+    is_timed: bool,
+) -> PartResult {
+    let (result, stats, memory) = run_timed(func, input, is_timed, |_| {});
+
+    PartResult {
+        day,
+        part,
+        answer: result.map(|x| x.to_string()),
+        stats,
+        memory,
+    }
+}
+
+/// A solution that is compiled into the main binary.
+/// Calling `run` with the puzzle input and whether to bench runs every part of the solution in-process.
+#[derive(Clone, Copy, Debug)]
+pub struct Solution {
+    pub puzzle: Puzzle,
+    pub run: fn(&str, bool) -> Vec<PartResult>,
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// With the `dhat-heap` feature, the heap usage of the first execution is returned as well.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    is_timed: bool,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, BenchStats, Option<MemoryStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        let result = func(input);
+        (result, MemoryStats::get())
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let stats = if is_timed {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        BenchStats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats, memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Heap usage of a single execution of a part, as reported by dhat.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Maximum number of bytes that were allocated at the same time.
+    pub peak_bytes: u64,
+    /// Number of bytes allocated over the whole execution.
+    pub total_bytes: u64,
+    /// Number of allocations over the whole execution.
+    pub allocations: u64,
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+impl MemoryStats {
+    /// Reads the stats of the running heap profiler.
+    #[cfg(feature = "dhat-heap")]
+    fn get() -> Option<Self> {
+        let stats = dhat::HeapStats::get();
+        Some(MemoryStats {
+            peak_bytes: stats.max_bytes as u64,
+            total_bytes: stats.total_bytes,
+            allocations: stats.total_blocks,
+        })
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    /// Heap usage is only tracked with the `dhat-heap` feature.
+    #[cfg(not(feature = "dhat-heap"))]
+    fn get() -> Option<Self> {
+        None
+    }
+}
+
+/// Summary statistics of a benchmark run, computed after outliers have been rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BenchStats {
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub stddev: Duration,
+    pub samples: u128,
+}
+
+impl BenchStats {
+    /// Stats for a single, un-benched execution.
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            min: duration,
+            median: duration,
+            p95: duration,
+            stddev: Duration::ZERO,
+            samples: 1,
+        }
+    }
+
+    /// Computes stats for a set of timings, discarding samples outside of the
+    /// interquartile fences (`Q1 - 1.5 * IQR`, `Q3 + 1.5 * IQR`).
+    fn from_timers(timers: &[Duration]) -> Self {
+        let mut sorted = timers.to_vec();
+        sorted.sort_unstable();
+
+        let q1 = percentile(&sorted, 25);
+        let q3 = percentile(&sorted, 75);
+        let fence = (q3 - q1).mul_f64(1.5);
+        let lower = q1.saturating_sub(fence);
+        let upper = q3 + fence;
+
+        sorted.retain(|t| *t >= lower && *t <= upper);
+
+        Self {
+            min: sorted[0],
+            median: percentile(&sorted, 50),
+            p95: percentile(&sorted, 95),
+            stddev: stddev(&sorted),
+            samples: sorted.len() as u128,
+        }
+    }
+}
+
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> BenchStats {
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+
+    // warm up caches and branch predictors before taking measurements.
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -76,11 +389,13 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    BenchStats::from_timers(&timers)
+}
+
+/// Nearest-rank percentile of a sorted, non-empty slice.
+fn percentile(sorted: &[Duration], p: usize) -> Duration {
+    let rank = (p * sorted.len()).div_ceil(100);
+    sorted[rank.saturating_sub(1).min(sorted.len() - 1)]
 }
 
 fn average_duration(numbers: &[Duration]) -> u128 {
@@ -91,14 +406,103 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+#[allow(clippy::cast_precision_loss)]
+fn stddev(numbers: &[Duration]) -> Duration {
+    let mean = average_duration(numbers) as f64;
+    let variance = numbers
+        .iter()
+        .map(|x| (x.as_nanos() as f64 - mean).powi(2))
+        .sum::<f64>()
+        / numbers.len() as f64;
+    Duration::from_secs_f64(variance.sqrt() / 1_000_000_000_f64)
+}
+
+/// The result of running a single solution part.
+/// Solution binaries emit this as line-delimited JSON when invoked with `--format json`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartResult {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub stats: BenchStats,
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartResult {
+    /// Serializes the result to a single line of JSON.
+    #[must_use]
+    pub fn to_json_line(&self) -> String {
+        JsonValue::from(self)
+            .stringify()
+            .expect("part result should be serializable")
+    }
+}
+
+/// Formats the result the same way a solution binary prints it in human-readable mode.
+impl Display for PartResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let part = format!("Part {}", self.part);
+        let memory_str = self.memory.as_ref().map_or(String::new(), format_memory);
+        let duration_str = format!("{}{memory_str}", format_duration(&self.stats));
+
+        match &self.answer {
+            Some(answer) if answer.contains('\n') => {
+                write!(f, "{part}: ▼ {duration_str}\n{answer}")
+            }
+            Some(answer) => write!(f, "{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}"),
+            None => write!(f, "{part}: ✖"),
+        }
+    }
+}
+
+#[must_use]
+pub fn format_duration(stats: &BenchStats) -> String {
+    let BenchStats {
+        min,
+        median,
+        p95,
+        stddev,
+        samples,
+    } = stats;
+
+    if *samples == 1 {
+        format!(" ({median:.1?})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({median:.1?} ± {stddev:.1?}, min {min:.1?}, p95 {p95:.1?} @ {samples} samples)")
     }
 }
 
+#[must_use]
+pub fn format_memory(memory: &MemoryStats) -> String {
+    format!(
+        " [peak {}, total {} in {} allocations]",
+        format_bytes(memory.peak_bytes),
+        format_bytes(memory.total_bytes),
+        memory.allocations
+    )
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[must_use]
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024_f64;
+    let mut unit = 0;
+
+    while value >= 1024_f64 && unit < UNITS.len() - 1 {
+        value /= 1024_f64;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -134,41 +538,363 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
+/// Try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured, see [`AocClient::from_env`].
+///  3. the answer is not known to be wrong and no cooldown is active, see [`submissions`].
+///
+/// Every submission is recorded in the submissions file, accepted answers also in the answers file, see [`answers`],
+/// and in the stars table of the readme.
 fn submit_result<T: Display>(
     result: T,
-    day: Day,
+    puzzle: Puzzle,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+) -> Result<Option<SubmitResponse>, Error> {
+    let result = result.to_string();
+
+    if let Err(refusal) = submissions::check_answer(puzzle, part, &result) {
+        eprintln!("Not submitting {result}: {refusal}");
+        return Ok(None);
+    }
+
+    let client = AocClient::from_env()?;
+
+    println!("Submitting result...");
+    let response = client.submit(puzzle, part, &result)?;
+    println!("{response}");
+
+    if let Err(e) = submissions::store_submission(puzzle, part, &result, &response) {
+        eprintln!("Failed to store submission: {e}");
+    }
+
+    if response.outcome == Outcome::Correct {
+        match answers::store_answer(puzzle, part, &result) {
+            Ok(()) => println!("Stored answer as known-good."),
+            Err(e) => eprintln!("Failed to store answer: {e}"),
+        }
+
+        if let Err(e) = readme_stars::update(puzzle.year) {
+            eprintln!("Failed to update the stars in the readme: {e}");
+        }
+    }
+
+    Ok(Some(response))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+fn nanos_to_json(duration: Duration) -> JsonValue {
+    JsonValue::Number(duration.as_nanos() as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn nanos_from_json(json: &HashMap<String, JsonValue>, key: &str) -> Result<Duration, String> {
+    json.get(key)
+        .and_then(|v| v.get::<f64>())
+        .map(|nanos| Duration::from_nanos(*nanos as u64))
+        .ok_or_else(|| format!("Expected result.{key} to be a number."))
+}
+
+impl From<&PartResult> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        map.insert("nanos".into(), nanos_to_json(value.stats.median));
+        map.insert("min_nanos".into(), nanos_to_json(value.stats.min));
+        map.insert("p95_nanos".into(), nanos_to_json(value.stats.p95));
+        map.insert("stddev_nanos".into(), nanos_to_json(value.stats.stddev));
+        map.insert(
+            "samples".into(),
+            JsonValue::Number(value.stats.samples as f64),
+        );
+
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected result to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected result.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .ok_or("Expected result.part to be a number.")?;
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .map(|samples| *samples as u128)
+            .ok_or("Expected result.samples to be a number.")?;
+
+        // memory stats are only reported by solutions built with the `dhat-heap` feature.
+        let memory = json.get("memory").map(MemoryStats::try_from).transpose()?;
+
+        Ok(PartResult {
+            day,
+            part,
+            answer: answer.cloned(),
+            stats: BenchStats {
+                min: nanos_from_json(json, "min_nanos")?,
+                median: nanos_from_json(json, "nanos")?,
+                p95: nanos_from_json(json, "p95_nanos")?,
+                stddev: nanos_from_json(json, "stddev_nanos")?,
+                samples,
+            },
+            memory,
+        })
     }
+}
+
+impl From<&MemoryStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "allocations".into(),
+            JsonValue::Number(value.allocations as f64),
+        );
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+        JsonValue::Object(map)
     }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory to be a JSON object.")?;
+
+        let get_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .map(|x| *x as u64)
+                .ok_or_else(|| format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: get_number("peak_bytes")?,
+            total_bytes: get_number("total_bytes")?,
+            allocations: get_number("allocations")?,
+        })
+    }
+}
+
+impl FromStr for PartResult {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("not a valid JSON line."))?;
+        PartResult::try_from(&json)
+    }
+}
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+/* -------------------------------------------------------------------------- */
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        format_bytes, format_duration, BenchStats, Format, InputSource, MemoryStats, PartResult,
+        RunOptions,
     };
+    use crate::day;
+    use crate::template::Error;
+    use std::time::Duration;
 
-    if part_submit != part {
-        return None;
+    fn parse_options(args: &[&str]) -> Result<RunOptions, Error> {
+        RunOptions::parse(pico_args::Arguments::from_vec(
+            args.iter().map(Into::into).collect(),
+        ))
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn parses_run_options() {
+        let options = parse_options(&[
+            "--time", "--input", "in.txt", "--part", "2", "--format", "json",
+        ])
+        .unwrap();
+        assert_eq!(
+            options,
+            RunOptions {
+                time: true,
+                submit: None,
+                input: InputSource::File("in.txt".into()),
+                part: Some(2),
+                format: Format::Json,
+            }
+        );
+        assert!(!options.runs_part(1));
+        assert!(options.runs_part(2));
+
+        let options = parse_options(&[]).unwrap();
+        assert_eq!(options, RunOptions::default());
+        assert!(options.runs_part(1));
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        let input = |args: &[&str]| parse_options(args).unwrap().input;
+        assert_eq!(input(&["--stdin"]), InputSource::Stdin);
+        assert_eq!(input(&["--example"]), InputSource::Example(None));
+        assert_eq!(
+            input(&["--example", "2", "--time"]),
+            InputSource::Example(Some(2))
+        );
+        assert_eq!(
+            parse_options(&["--submit", "1"]).unwrap().input,
+            InputSource::Puzzle
+        );
+    }
+
+    #[test]
+    fn rejects_bad_run_options() {
+        for args in [
+            &["--submit"][..],
+            &["--submit", "3"],
+            &["--part", "one"],
+            &["--format", "yaml"],
+            &["--bench"],
+            &["--stdin", "--example"],
+            &["--example", "two"],
+            &["--submit", "1", "--example"],
+        ] {
+            assert!(
+                matches!(parse_options(args), Err(Error::Usage(_))),
+                "{args:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn computes_bench_statistics() {
+        let timers: Vec<Duration> = (1..=20).map(Duration::from_micros).collect();
+        let stats = BenchStats::from_timers(&timers);
+        assert_eq!(stats.min, Duration::from_micros(1));
+        assert_eq!(stats.median, Duration::from_micros(10));
+        assert_eq!(stats.p95, Duration::from_micros(19));
+        assert_eq!(stats.samples, 20);
+        assert_eq!(stats.stddev.as_nanos(), 5766);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let mut timers = vec![Duration::from_micros(10); 19];
+        timers.push(Duration::from_millis(10));
+        let stats = BenchStats::from_timers(&timers);
+        assert_eq!(stats.samples, 19);
+        assert_eq!(stats.p95, Duration::from_micros(10));
+        assert_eq!(stats.stddev, Duration::ZERO);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn formats_single_runs() {
+        let stats = BenchStats::single(Duration::from_micros(10));
+        assert_eq!(format_duration(&stats), " (10.0µs)");
+    }
+
+    #[test]
+    fn formats_bench_statistics() {
+        let stats = BenchStats {
+            min: Duration::from_micros(9),
+            median: Duration::from_micros(10),
+            p95: Duration::from_micros(12),
+            stddev: Duration::from_nanos(500),
+            samples: 100,
+        };
+        assert_eq!(
+            format_duration(&stats),
+            " (10.0µs ± 500.0ns, min 9.0µs, p95 12.0µs @ 100 samples)"
+        );
+    }
+
+    #[test]
+    fn serializes_part_results() {
+        let result = PartResult {
+            day: day!(3),
+            part: 2,
+            answer: Some("(42)\n samples)".into()),
+            stats: BenchStats {
+                min: Duration::from_nanos(90),
+                median: Duration::from_nanos(100),
+                p95: Duration::from_nanos(120),
+                stddev: Duration::from_nanos(5),
+                samples: 1000,
+            },
+            memory: Some(MemoryStats {
+                peak_bytes: 1024,
+                total_bytes: 4096,
+                allocations: 3,
+            }),
+        };
+        let line = result.to_json_line();
+        assert_eq!(line.contains('\n'), false);
+        assert_eq!(line.parse::<PartResult>().unwrap(), result);
+    }
+
+    #[test]
+    fn deserializes_unsolved_parts() {
+        let line = r#"{"day":"01","part":1,"answer":null,"nanos":10,"min_nanos":10,"p95_nanos":10,"stddev_nanos":0,"samples":1}"#;
+        let result = line.parse::<PartResult>().unwrap();
+        assert_eq!(result.answer, None);
+        assert_eq!(result.stats.median, Duration::from_nanos(10));
+        assert_eq!(result.memory, None);
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn rejects_human_readable_lines() {
+        assert!("Part 1: 0 (74.13ns @ 100000 samples)"
+            .parse::<PartResult>()
+            .is_err());
+    }
 }
@@ -0,0 +1,418 @@
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs, io,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::{Outcome, SubmitResponse};
+use crate::template::{data_dir, Day, Error, Puzzle};
+
+/// Represents a single submitted answer and what the server said about it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: Outcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Seconds to wait before the next answer is accepted.
+    pub wait: Option<u64>,
+}
+
+/// Reason why an answer is not submitted.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    KnownWrong(Outcome),
+    TooHigh(String),
+    TooLow(String),
+    Cooldown(Duration),
+    /// The submissions file could not be parsed, so the answer can not be checked.
+    UnreadableHistory(String),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part is already solved with answer {answer}.")
+            }
+            Refusal::KnownWrong(outcome) => {
+                write!(f, "this answer was already submitted and was {outcome}.")
+            }
+            Refusal::TooHigh(bound) => {
+                write!(f, "{bound} was already too high, so this answer is too.")
+            }
+            Refusal::TooLow(bound) => {
+                write!(f, "{bound} was already too low, so this answer is too.")
+            }
+            Refusal::Cooldown(wait) => {
+                write!(f, "wait {}s before submitting again.", wait.as_secs())
+            }
+            Refusal::UnreadableHistory(e) => {
+                write!(f, "could not parse {e} Fix or remove the file to submit.")
+            }
+        }
+    }
+}
+
+/// Represents the history of submitted answers of a season.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Path of the submissions file of a season, e.g. `data/submissions.json`.
+    pub fn get_path(year: Option<u16>) -> String {
+        format!("{}/submissions.json", data_dir(year))
+    }
+
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self, year: Option<u16>) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(Submissions::get_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty history.
+    /// A file that can not be parsed is an error, so it is not overwritten and its history is not lost.
+    pub fn read_from_file(year: Option<u16>) -> Result<Self, String> {
+        let path = Submissions::get_path(year);
+        match fs::read_to_string(&path) {
+            Ok(s) => Submissions::try_from(s).map_err(|e| format!("{path}: {e}")),
+            Err(_) => Ok(Submissions::default()),
+        }
+    }
+
+    /// Checks an answer against earlier submissions of the part and the cooldown of the last submission,
+    /// `now` is given in seconds since the unix epoch.
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let history: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if let Some(correct) = history.iter().find(|s| s.outcome == Outcome::Correct) {
+            return Err(Refusal::AlreadySolved(correct.answer.clone()));
+        }
+
+        if let Some(wrong) = history
+            .iter()
+            .find(|s| s.answer == answer && s.outcome.is_wrong())
+        {
+            return Err(Refusal::KnownWrong(wrong.outcome));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |outcome: Outcome| {
+                history
+                    .iter()
+                    .filter(move |s| s.outcome == outcome)
+                    .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, &s.answer)))
+            };
+
+            if let Some((_, high)) = bound(Outcome::TooHigh).filter(|(x, _)| value >= *x).min() {
+                return Err(Refusal::TooHigh(high.clone()));
+            }
+
+            if let Some((_, low)) = bound(Outcome::TooLow).filter(|(x, _)| value <= *x).max() {
+                return Err(Refusal::TooLow(low.clone()));
+            }
+        }
+
+        // the cooldown applies to every puzzle, so it is taken from the last submission of the season.
+        if let Some(last) = self.data.iter().max_by_key(|s| s.timestamp) {
+            let until = last.timestamp + last.wait.unwrap_or(0);
+            if until > now {
+                return Err(Refusal::Cooldown(Duration::from_secs(until - now)));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Checks whether an answer may be submitted, see [`Submissions::check`].
+pub fn check_answer(puzzle: Puzzle, part: u8, answer: &str) -> Result<(), Refusal> {
+    Submissions::read_from_file(puzzle.year)
+        .map_err(Refusal::UnreadableHistory)?
+        .check(puzzle.day, part, answer, now())
+}
+
+/// Appends a submitted answer to the submissions file of the puzzle's season.
+pub fn store_submission(
+    puzzle: Puzzle,
+    part: u8,
+    answer: &str,
+    response: &SubmitResponse,
+) -> Result<(), Error> {
+    let mut submissions = Submissions::read_from_file(puzzle.year).map_err(Error::Parse)?;
+    submissions.data.push(Submission {
+        day: puzzle.day,
+        part,
+        answer: answer.into(),
+        outcome: response.outcome,
+        timestamp: now(),
+        wait: response.wait.map(|wait| wait.as_secs()),
+    });
+    Ok(submissions.store_file(puzzle.year)?)
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn outcome_to_str(outcome: Outcome) -> &'static str {
+    match outcome {
+        Outcome::Correct => "correct",
+        Outcome::Incorrect => "incorrect",
+        Outcome::TooHigh => "too_high",
+        Outcome::TooLow => "too_low",
+        Outcome::Wait => "wait",
+        Outcome::AlreadySolved => "already_solved",
+    }
+}
+
+fn outcome_from_str(s: &str) -> Option<Outcome> {
+    match s {
+        "correct" => Some(Outcome::Correct),
+        "incorrect" => Some(Outcome::Incorrect),
+        "too_high" => Some(Outcome::TooHigh),
+        "too_low" => Some(Outcome::TooLow),
+        "wait" => Some(Outcome::Wait),
+        "already_solved" => Some(Outcome::AlreadySolved),
+        _ => None,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(outcome_to_str(value.outcome).into()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "wait".into(),
+            match value.wait {
+                Some(wait) => JsonValue::Number(wait as f64),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8)
+            .filter(|part| (1..=2).contains(part))
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .and_then(|outcome| outcome_from_str(outcome))
+            .ok_or("Expected submission.outcome to be a known outcome.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        let wait = json
+            .get("wait")
+            .map(|v| if v.is_null() { None } else { v.get::<f64>() })
+            .ok_or("Expected submission.wait to be null or a number.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            outcome,
+            timestamp: *timestamp as u64,
+            wait: wait.map(|wait| *wait as u64),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Refusal, Submission, Submissions};
+    use crate::{day, template::aoc_client::Outcome};
+
+    fn submission(part: u8, answer: &str, outcome: Outcome, timestamp: u64) -> Submission {
+        Submission {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            outcome,
+            timestamp,
+            wait: None,
+        }
+    }
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                submission(1, "100", Outcome::TooHigh, 10),
+                submission(1, "20", Outcome::TooLow, 20),
+                submission(1, "abc", Outcome::Incorrect, 30),
+            ],
+        }
+    }
+
+    #[test]
+    fn allows_new_answers_within_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(submissions.check(day!(1), 1, "50", 100), Ok(()));
+        assert_eq!(submissions.check(day!(1), 2, "100", 100), Ok(()));
+        assert_eq!(submissions.check(day!(2), 1, "abc", 100), Ok(()));
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "abc", 100),
+            Err(Refusal::KnownWrong(Outcome::Incorrect))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "100", 100),
+            Err(Refusal::KnownWrong(Outcome::TooHigh))
+        );
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "101", 100),
+            Err(Refusal::TooHigh("100".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "-5", 100),
+            Err(Refusal::TooLow("20".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let mut submissions = get_mock_submissions();
+        submissions
+            .data
+            .push(submission(1, "42", Outcome::Correct, 40));
+        assert_eq!(
+            submissions.check(day!(1), 1, "43", 100),
+            Err(Refusal::AlreadySolved("42".into()))
+        );
+    }
+
+    #[test]
+    fn respects_cooldown() {
+        let mut submissions = get_mock_submissions();
+        submissions.data.push(Submission {
+            wait: Some(60),
+            ..submission(2, "1", Outcome::Incorrect, 100)
+        });
+        assert_eq!(
+            submissions.check(day!(3), 1, "1", 130),
+            Err(Refusal::Cooldown(Duration::from_secs(30)))
+        );
+        assert_eq!(submissions.check(day!(3), 1, "1", 160), Ok(()));
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let mut submissions = get_mock_submissions();
+        submissions.data[0].wait = Some(60);
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Submissions::try_from(json).unwrap().data, submissions.data);
+    }
+}
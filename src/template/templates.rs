@@ -0,0 +1,154 @@
+/// Module templates used by `scaffold`, loaded from the `templates` directory of the project.
+use std::{env, fs, io};
+
+use crate::template::Puzzle;
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// Used if the project does not have a default template.
+const EMBEDDED_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/templates/default.txt"
+));
+
+/// Directory of the templates, `templates` unless overridden with `AOC_TEMPLATE_DIR`.
+pub fn get_dir() -> String {
+    env::var("AOC_TEMPLATE_DIR").unwrap_or_else(|_| "templates".into())
+}
+
+/// Path of a template, e.g. `templates/grid.txt`.
+pub fn get_path(name: &str) -> String {
+    format!("{}/{name}.txt", get_dir())
+}
+
+/// Names of all templates in the template directory.
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(get_dir())
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    names.sort_unstable();
+    names
+}
+
+/// Loads a template by name.
+pub fn load(name: &str) -> Result<String, io::Error> {
+    match fs::read_to_string(get_path(name)) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound && name == DEFAULT_TEMPLATE => {
+            Ok(EMBEDDED_TEMPLATE.into())
+        }
+        result => result,
+    }
+}
+
+/// Values of the placeholders in a template.
+pub struct Placeholders {
+    pub puzzle: Puzzle,
+    pub title: Option<String>,
+    pub answer_type: String,
+}
+
+impl Placeholders {
+    /// Placeholders for a puzzle, the title is read from the downloaded puzzle description if present.
+    pub fn new(puzzle: Puzzle, answer_type: &str) -> Self {
+        let title = fs::read_to_string(puzzle.puzzle_path())
+            .ok()
+            .and_then(|description| parse_title(&description));
+
+        Self {
+            puzzle,
+            title,
+            answer_type: answer_type.into(),
+        }
+    }
+}
+
+/// Replaces the placeholders of a template:
+///  - `%DAY_NUMBER%`: the day, e.g. `1`.
+///  - `%DAY%`: the zero-padded day, e.g. `01`.
+///  - `%YEAR%`: the year of the puzzle, e.g. `2023`.
+///  - `%TITLE%`: the title of the puzzle, e.g. `Trebuchet?!`, or `Day 1` if it was not downloaded yet.
+///  - `%ANSWER_TYPE%`: the type returned by both parts, e.g. `u32`.
+pub fn render(template: &str, placeholders: &Placeholders) -> String {
+    let day = placeholders.puzzle.day;
+    let year = placeholders
+        .puzzle
+        .resolved_year()
+        .map_or(String::new(), |year| year.to_string());
+    let title = placeholders
+        .title
+        .clone()
+        .unwrap_or_else(|| format!("Day {}", day.into_inner()));
+
+    template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%DAY%", &day.to_string())
+        .replace("%YEAR%", &year)
+        .replace("%TITLE%", &title)
+        .replace("%ANSWER_TYPE%", &placeholders.answer_type)
+}
+
+/// Parses the title of a puzzle description, e.g. `Trebuchet?!` for `--- Day 1: Trebuchet?! ---`.
+fn parse_title(description: &str) -> Option<String> {
+    let line = description.lines().find(|line| line.contains("--- Day "))?;
+    let title = line
+        .trim()
+        .trim_start_matches('\\')
+        .trim_matches('-')
+        .trim();
+    let (_, title) = title.split_once(": ")?;
+    Some(title.to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_title, render, Placeholders};
+    use crate::{day, template::Puzzle};
+
+    #[test]
+    fn parses_title() {
+        let description = "\\--- Day 1: Trebuchet?! ---\n----------\n\nSomething is wrong.";
+        assert_eq!(parse_title(description), Some("Trebuchet?!".into()));
+        assert_eq!(parse_title("Something is wrong."), None);
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            puzzle: Puzzle {
+                year: Some(2022),
+                day: day!(3),
+            },
+            title: Some("Rucksack Reorganization".into()),
+            answer_type: "u64".into(),
+        };
+        let rendered = render(
+            "//! %TITLE% (%YEAR%, %DAY%)\nsolution!(%DAY_NUMBER%);\nOption<%ANSWER_TYPE%>",
+            &placeholders,
+        );
+        assert_eq!(
+            rendered,
+            "//! Rucksack Reorganization (2022, 03)\nsolution!(3);\nOption<u64>"
+        );
+    }
+
+    #[test]
+    fn renders_title_fallback() {
+        let placeholders = Placeholders {
+            puzzle: Puzzle::from(day!(12)),
+            title: None,
+            answer_type: "u32".into(),
+        };
+        assert_eq!(render("%TITLE%", &placeholders), "Day 12");
+    }
+}
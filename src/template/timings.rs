@@ -1,37 +1,104 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fmt::Display, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::history::Run;
+use crate::template::runner::MemoryStats;
+use crate::template::{data_dir, Day};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+/// Version of the timings file. Files without a version are version 1, which had no history.
+/// Up to version 2, durations were stored as formatted strings, e.g. `74.1ms`.
+pub const TIMINGS_VERSION: u32 = 3;
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
-    pub total_nanos: f64,
+    /// Median duration of a part.
+    pub part_1: Option<Duration>,
+    pub part_2: Option<Duration>,
+    /// Standard deviation of the benchmark samples.
+    pub part_1_spread: Option<Duration>,
+    pub part_2_spread: Option<Duration>,
+    /// Heap usage of a part, only present if recorded with `cargo time --dhat`.
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
+    /// Sum of the medians of both parts.
+    pub total: Duration,
+    pub status: Status,
 }
 
-/// Represents benchmark times for a set of days.
+/// Outcome of running the solution of a day.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Status {
+    #[default]
+    Ok,
+    /// The solution ran, but did not return an answer for any part.
+    Unsolved,
+    Panic,
+    Timeout,
+    /// The solution could not be built or run, e.g. because it does not compile.
+    Error,
+}
+
+impl Status {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Unsolved => "unsolved",
+            Status::Panic => "panic",
+            Status::Timeout => "timeout",
+            Status::Error => "error",
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Status {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ok" => Ok(Status::Ok),
+            "unsolved" => Ok(Status::Unsolved),
+            "panic" => Ok(Status::Panic),
+            "timeout" => Ok(Status::Timeout),
+            "error" => Ok(Status::Error),
+            s => Err(format!("unknown status `{s}`.")),
+        }
+    }
+}
+
+/// Represents benchmark times for the days of a season.
 /// Can be serialized from / to JSON.
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
+    /// The latest timing of each day.
     pub data: Vec<Timing>,
+    /// Every stored run, oldest first.
+    pub history: Vec<Run>,
 }
 
 impl Timings {
+    /// Path of the timings file of a season, e.g. `data/timings.json`.
+    pub fn get_path(year: Option<u16>) -> String {
+        format!("{}/timings.json", data_dir(year))
+    }
+
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self, year: Option<u16>) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(Timings::get_path(year))?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    pub fn read_from_file(year: Option<u16>) -> Self {
+        let s = fs::read_to_string(Timings::get_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -45,11 +112,27 @@ impl Timings {
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Memory usage is only measured by dhat runs, so it is kept from `self` if `other` did not measure it.
+    /// A day that did not complete in `other` keeps the timings of `self`, only its status is updated.
+    /// The runs of `other` are appended to the history of `self`.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let timing = match self.data.iter().find(|t| t.day == timing.day) {
+                Some(old) if timing.status != Status::Ok => Timing {
+                    status: timing.status,
+                    ..old.clone()
+                },
+                Some(old) => Timing {
+                    part_1_memory: timing.part_1_memory.or(old.part_1_memory),
+                    part_2_memory: timing.part_2_memory.or(old.part_2_memory),
+                    ..timing.clone()
+                },
+                None => timing.clone(),
+            };
+
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -58,13 +141,52 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|a| a.day);
+
+        let history = self.history.iter().chain(&new.history).cloned().collect();
+        Timings { data, history }
+    }
+
+    /// Sum up total duration of timings.
+    pub fn total(&self) -> Duration {
+        self.data.iter().map(|x| x.total).sum()
     }
 
     /// Sum up total duration of timings as millis.
     pub fn total_millis(&self) -> f64 {
-        self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
+        self.total().as_secs_f64() * 1000_f64
+    }
+
+    /// Compares the benched parts of `new` to the parts stored in `self`.
+    /// Parts that are missing from either set of timings are skipped,
+    /// unless the day of a stored part did not complete in `new`.
+    pub fn compare(&self, new: &Self) -> Vec<TimingChange> {
+        let mut changes = vec![];
+
+        for timing in &new.data {
+            let Some(stored) = self.data.iter().find(|t| t.day == timing.day) else {
+                continue;
+            };
+
+            for part in [1, 2] {
+                let Some(old) = stored.part(part) else {
+                    continue;
+                };
+
+                if timing.status != Status::Ok || timing.part(part).is_some() {
+                    changes.push(TimingChange {
+                        day: timing.day,
+                        part,
+                        old,
+                        new: timing.part(part),
+                        status: timing.status,
+                    });
+                }
+            }
+        }
+
+        changes.sort_unstable_by_key(|c| (c.day, c.part));
+        changes
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
@@ -74,16 +196,138 @@ impl Timings {
     }
 }
 
+impl Timing {
+    /// A timing of a day without benched parts.
+    pub fn new(day: Day, status: Status) -> Self {
+        Timing {
+            day,
+            part_1: None,
+            part_2: None,
+            part_1_spread: None,
+            part_2_spread: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            total: Duration::ZERO,
+            status,
+        }
+    }
+
+    /// Returns the benched duration of a part.
+    pub fn part(&self, part: u8) -> Option<Duration> {
+        match part {
+            1 => self.part_1,
+            2 => self.part_2,
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+impl Timing {
+    /// A timing of a day that ran successfully, the total is the sum of the parts.
+    pub fn ok(day: Day, part_1: Option<Duration>, part_2: Option<Duration>) -> Self {
+        Timing {
+            part_1,
+            part_2,
+            total: part_1.unwrap_or_default() + part_2.unwrap_or_default(),
+            ..Timing::new(day, Status::Ok)
+        }
+    }
+}
+
+/// Represents the change of a part's benchmark time between two sets of timings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingChange {
+    pub day: Day,
+    pub part: u8,
+    pub old: Duration,
+    /// The new duration, `None` if the day did not complete.
+    pub new: Option<Duration>,
+    pub status: Status,
+}
+
+impl TimingChange {
+    /// Relative change of the duration in percent. Positive values are regressions.
+    /// `None` if the day did not complete.
+    pub fn percent(&self) -> Option<f64> {
+        let new = self.new?;
+        if self.old.is_zero() {
+            return Some(0_f64);
+        }
+        let old = self.old.as_secs_f64();
+        Some((new.as_secs_f64() - old) / old * 100_f64)
+    }
+
+    /// Whether the part got slower by more than `threshold` percent or its day did not complete.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent().is_none_or(|percent| percent > threshold)
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/// Parses a `Debug`-formatted duration (e.g. `74.1ms`), as stored by timing files up to version 2.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_duration(s: &str) -> Option<Duration> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let nanos = match s.trim() {
+        s if s.contains("ns") => parse_to_float(s, "ns"),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }?;
+    Some(Duration::from_nanos(nanos.round() as u64))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn duration_to_json(duration: Duration) -> JsonValue {
+    JsonValue::Number(duration.as_nanos() as f64)
+}
+
+/// Parses a duration stored as nanos, or as a formatted string by older timing files.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn duration_from_json(value: &JsonValue) -> Option<Duration> {
+    match value {
+        JsonValue::Number(nanos) if *nanos >= 0_f64 => Some(Duration::from_nanos(*nanos as u64)),
+        JsonValue::String(s) => parse_duration(s),
+        _ => None,
+    }
+}
+
+/// Parses a duration of a timing that is null or missing if the part was not benched.
+fn optional_duration(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+) -> Result<Option<Duration>, String> {
+    match json.get(key) {
+        None => Ok(None),
+        Some(v) if v.is_null() => Ok(None),
+        Some(v) => duration_from_json(v)
+            .map(Some)
+            .ok_or_else(|| format!("Expected timing.{key} to be null or a duration.")),
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(TIMINGS_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
+        map.insert(
+            "history".into(),
+            JsonValue::Array(value.history.iter().map(JsonValue::from).collect()),
+        );
 
         JsonValue::Object(map)
     }
@@ -92,23 +336,55 @@ impl From<Timings> for JsonValue {
 impl TryFrom<String> for Timings {
     type Error = String;
 
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = match json.get("version") {
+            Some(v) => v
+                .get::<f64>()
+                .map(|v| *v as u32)
+                .ok_or("expected `json.version` to be a number.")?,
+            None => 1,
+        };
+
+        if version > TIMINGS_VERSION {
+            return Err(format!(
+                "timings file has version {version}, this template supports up to version {TIMINGS_VERSION}."
+            ));
+        }
+
+        let data: Vec<Timing> = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
-            .ok_or("expected `json.data` to be an array.")?;
-
-        Ok(Timings {
-            data: json_data
+            .ok_or("expected `json.data` to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        // version 1 only kept the latest timings, they become the first entry of the history.
+        let history = if version == 1 {
+            if data.is_empty() {
+                vec![]
+            } else {
+                vec![Run::migrate(&data)]
+            }
+        } else {
+            json.get("history")
+                .ok_or("expected JSON document to have key `history`.")?
+                .get::<Vec<JsonValue>>()
+                .ok_or("expected `json.history` to be an array.")?
                 .iter()
-                .map(Timing::try_from)
-                .collect::<Result<_, _>>()?,
-        })
+                .map(Run::try_from)
+                .collect::<Result<_, _>>()?
+        };
+
+        Ok(Timings { data, history })
     }
 }
 
@@ -119,26 +395,29 @@ impl From<&Timing> for JsonValue {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
-
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
-
+        map.insert("total_nanos".into(), duration_to_json(value.total));
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
         );
 
-        map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
-        );
+        for (key, value) in [
+            ("part_1", &value.part_1),
+            ("part_2", &value.part_2),
+            ("part_1_spread", &value.part_1_spread),
+            ("part_2_spread", &value.part_2_spread),
+        ] {
+            map.insert(key.into(), value.map_or(JsonValue::Null, duration_to_json));
+        }
+
+        for (key, value) in [
+            ("part_1_memory", &value.part_1_memory),
+            ("part_2_memory", &value.part_2_memory),
+        ] {
+            if let Some(memory) = value {
+                map.insert(key.into(), JsonValue::from(memory));
+            }
+        }
 
         JsonValue::Object(map)
     }
@@ -158,26 +437,54 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+        for key in ["part_1", "part_2"] {
+            if !json.contains_key(key) {
+                return Err(format!("Expected timing.{key} to be null or a duration."));
+            }
+        }
+
+        let part_1 = optional_duration(json, "part_1")?;
+        let part_2 = optional_duration(json, "part_2")?;
 
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        // spreads were added later and may be missing from older timing files.
+        let part_1_spread = optional_duration(json, "part_1_spread")?;
+        let part_2_spread = optional_duration(json, "part_2_spread")?;
 
-        let total_nanos = json
+        // memory stats are optional, they are only recorded with `cargo time --dhat`.
+        let part_1_memory = json
+            .get("part_1_memory")
+            .map(MemoryStats::try_from)
+            .transpose()?;
+        let part_2_memory = json
+            .get("part_2_memory")
+            .map(MemoryStats::try_from)
+            .transpose()?;
+
+        let total = json
             .get("total_nanos")
-            .and_then(|v| v.get::<f64>().copied())
+            .filter(|v| v.get::<f64>().is_some())
+            .and_then(duration_from_json)
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // statuses were added later, timings without one are from successful runs.
+        let status = match json.get("status") {
+            Some(v) => v
+                .get::<String>()
+                .and_then(|s| Status::from_str(s).ok())
+                .ok_or("Expected timing.status to be a status string.")?,
+            None => Status::Ok,
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
-            total_nanos,
+            part_1,
+            part_2,
+            part_1_spread,
+            part_2_spread,
+            part_1_memory,
+            part_2_memory,
+            total,
+            status,
         })
     }
 }
@@ -186,6 +493,8 @@ impl TryFrom<&JsonValue> for Timing {
 
 #[cfg(feature = "test_lib")]
 mod tests {
+    use std::time::Duration;
+
     use crate::day;
 
     use super::{Timing, Timings};
@@ -193,30 +502,29 @@ mod tests {
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: None,
-                    total_nanos: 4e+10,
-                },
+                Timing::ok(
+                    day!(1),
+                    Some(Duration::from_millis(10)),
+                    Some(Duration::from_millis(20)),
+                ),
+                Timing::ok(
+                    day!(2),
+                    Some(Duration::from_millis(30)),
+                    Some(Duration::from_millis(40)),
+                ),
+                Timing::ok(day!(4), Some(Duration::from_millis(40)), None),
             ],
+            history: vec![],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::timings::{Status, Timings},
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -225,9 +533,102 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(Duration::from_millis(1)));
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.part_1_spread, None);
+            assert_eq!(timing.total, Duration::from_secs(1));
+            assert_eq!(timing.status, Status::Ok);
+        }
+
+        #[test]
+        fn handles_json_timings_with_nanos() {
+            let json = r#"{ "version": 3, "data": [{ "day": "01", "part_1": 74130000, "part_2": null, "part_1_spread": 7413000, "part_2_spread": null, "total_nanos": 74130000 }], "history": [] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1, Some(Duration::from_micros(74_130)));
+            assert_eq!(timing.part_1_spread, Some(Duration::from_micros(7_413)));
+            assert_eq!(timing.total, Duration::from_micros(74_130));
+        }
+
+        #[test]
+        fn handles_formatted_durations() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5µs", "part_2": "2s", "part_1_spread": "74.1ms", "total_nanos": 0 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1, Some(Duration::from_nanos(1500)));
+            assert_eq!(timing.part_2, Some(Duration::from_secs(2)));
+            assert_eq!(timing.part_1_spread, Some(Duration::from_micros(74_100)));
+        }
+
+        #[test]
+        fn handles_json_timings_with_status() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": null, "part_2": null, "total_nanos": 0, "status": "timeout" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].status, Status::Timeout);
+        }
+
+        #[test]
+        fn parses_status_strings() {
+            for status in [
+                Status::Ok,
+                Status::Unsolved,
+                Status::Panic,
+                Status::Timeout,
+                Status::Error,
+            ] {
+                assert_eq!(status.as_str().parse(), Ok(status));
+            }
+        }
+
+        #[test]
+        fn handles_json_timings_with_spread() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "part_1_spread": "2.0µs", "part_2_spread": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_spread, Some(Duration::from_micros(2)));
+            assert_eq!(timing.part_2_spread, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_memory() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "part_1_memory": { "peak_bytes": 2048, "total_bytes": 4096, "allocations": 12 }, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.part_1_memory.map(|m| m.peak_bytes), Some(2048));
+            assert_eq!(timing.part_1_memory.map(|m| m.allocations), Some(12));
+            assert_eq!(timing.part_2_memory, None);
+        }
+
+        #[test]
+        fn migrates_timings_without_version() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.history.len(), 1);
+
+            let run = &timings.history[0];
+            assert_eq!(run.commit, None);
+            assert_eq!(run.days[0].day, day!(1));
+            assert_eq!(
+                run.days[0].part_1.map(|p| p.median),
+                Some(Duration::from_millis(1))
+            );
+            assert_eq!(run.days[0].part_2, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_history() {
+            let json = r#"{ "version": 2, "data": [], "history": [{ "timestamp": 1701388800, "commit": "1a2b3c4", "rustc": null, "machine": "ci", "days": [{ "day": "01", "status": "ok", "part_1": { "nanos": 1500, "samples": 100 }, "part_2": null }] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let run = &timings.history[0];
+            assert_eq!(run.timestamp, Some(1_701_388_800));
+            assert_eq!(run.machine.as_deref(), Some("ci"));
+            assert_eq!(run.days[0].part_1.and_then(|p| p.samples), Some(100));
+        }
+
+        #[test]
+        fn rejects_newer_versions() {
+            let json = r#"{ "version": 4, "data": [], "history": [] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
         }
 
         #[test]
@@ -276,6 +677,8 @@ mod tests {
     }
 
     mod is_day_complete {
+        use std::time::Duration;
+
         use crate::{
             day,
             template::timings::{Timing, Timings},
@@ -284,50 +687,48 @@ mod tests {
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
-                    total_nanos: 3_000_000_000_f64,
-                }],
+                data: vec![Timing::ok(
+                    day!(1),
+                    Some(Duration::from_millis(1)),
+                    Some(Duration::from_millis(2)),
+                )],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: None,
-                    total_nanos: 1_000_000_000_f64,
-                }],
+                data: vec![Timing::ok(day!(1), Some(Duration::from_millis(1)), None)],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
-                data: vec![Timing {
-                    day: day!(1),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0.0,
-                }],
+                data: vec![Timing::ok(day!(1), None, None)],
+                history: vec![],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
+        use std::time::Duration;
+
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                history::Run,
+                runner::MemoryStats,
+                timings::{Status, Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -336,12 +737,8 @@ mod tests {
         fn handles_disjunct_timings() {
             let timings = get_mock_timings();
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(3),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::ok(day!(3), None, None)],
+                history: vec![],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -356,22 +753,59 @@ mod tests {
             let timings = get_mock_timings();
 
             let other = Timings {
-                data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
-                }],
+                data: vec![Timing::ok(day!(2), None, None)],
+                history: vec![],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total, Duration::ZERO);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_memory_of_overlapping_timings() {
+            let memory = MemoryStats {
+                peak_bytes: 2048,
+                total_bytes: 4096,
+                allocations: 12,
+            };
+
+            let mut timings = get_mock_timings();
+            timings.data[1].part_1_memory = Some(memory);
+            timings.data[1].part_2_memory = Some(memory);
+
+            let new_memory = MemoryStats {
+                allocations: 6,
+                ..memory
+            };
+
+            let mut other = get_mock_timings();
+            other.data[1].part_1_memory = Some(new_memory);
+            other.data[1].part_2_memory = None;
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[1].part_1_memory, Some(new_memory));
+            assert_eq!(merged.data[1].part_2_memory, Some(memory));
+        }
+
+        #[test]
+        fn keeps_timings_of_days_that_did_not_complete() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![Timing::new(day!(2), Status::Panic)],
+                history: vec![],
+            };
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.data[1].status, Status::Panic);
+            assert_eq!(merged.data[1].part_1, timings.data[1].part_1);
+            assert_eq!(merged.data[1].part_2, timings.data[1].part_2);
+            assert_eq!(merged.data[1].total, timings.data[1].total);
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -387,5 +821,89 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn appends_history() {
+            let mut timings = get_mock_timings();
+            timings.history.push(Run::migrate(&timings.data));
+
+            let mut other = Timings::default();
+            other.history.push(Run::migrate(&[]));
+
+            let merged = timings.merge(&other);
+            assert_eq!(merged.history.len(), 2);
+            assert_eq!(merged.history[0].days.len(), 3);
+            assert_eq!(merged.history[1].days.len(), 0);
+        }
+    }
+
+    mod compare {
+        use std::time::Duration;
+
+        use crate::{
+            day,
+            template::timings::{Status, Timing, Timings},
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn compares_overlapping_parts() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![
+                    Timing::ok(
+                        day!(2),
+                        Some(Duration::from_millis(33)),
+                        Some(Duration::from_millis(20)),
+                    ),
+                    Timing::ok(day!(4), None, Some(Duration::from_millis(1))),
+                ],
+                history: vec![],
+            };
+
+            let changes = timings.compare(&other);
+            assert_eq!(changes.len(), 2);
+            assert_eq!(changes[0].day, day!(2));
+            assert_eq!(changes[0].part, 1);
+            assert!((changes[0].percent().unwrap() - 10_f64).abs() < 1e-6);
+            assert_eq!(changes[1].part, 2);
+            assert!((changes[1].percent().unwrap() + 50_f64).abs() < 1e-6);
+        }
+
+        #[test]
+        fn reports_days_that_did_not_complete() {
+            let timings = get_mock_timings();
+            let other = Timings {
+                data: vec![Timing::new(day!(4), Status::Timeout)],
+                history: vec![],
+            };
+
+            let changes = timings.compare(&other);
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].part, 1);
+            assert_eq!(changes[0].new, None);
+            assert_eq!(changes[0].status, Status::Timeout);
+            assert!(changes[0].is_regression(10_f64));
+        }
+
+        #[test]
+        fn skips_days_without_stored_timings() {
+            let timings = Timings::default();
+            let other = get_mock_timings();
+            assert_eq!(timings.compare(&other).len(), 0);
+        }
+
+        #[test]
+        fn returns_part_durations() {
+            let timing = Timing::ok(
+                day!(1),
+                Some(Duration::from_nanos(1500)),
+                Some(Duration::from_secs(2)),
+            );
+            assert_eq!(timing.part(1), Some(Duration::from_nanos(1500)));
+            assert_eq!(timing.part(2), Some(Duration::from_secs(2)));
+            assert_eq!(timing.part(3), None);
+        }
     }
 }